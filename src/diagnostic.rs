@@ -0,0 +1,237 @@
+/*
+ * diagnostic.rs
+ *
+ * Renders parse and runtime Errors with the file, line and column, the
+ * source line and a caret underline.  Colour is only used on a terminal
+ * and never when NO_COLOR is set.
+ */
+use crate::error::Error;
+use crate::interpreter::Frame;
+use crate::parser::Span;
+use std::fmt::Write;
+use std::io::IsTerminal;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub code: &'static str,
+    pub message: String,
+    pub span: Option<Span>,
+    // Text after the carets.
+    pub label: Option<String>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(code: &'static str, message: &str) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            span: None,
+            label: None,
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    /// Renders the Diagnostic against `source`, the contents of `file`.
+    pub fn render(&self, file: &str, source: &str, color: bool) -> String {
+        let paint = |style: &str, text: &str| match color {
+            true => format!("\x1b[{}m{}\x1b[0m", style, text),
+            false => text.to_string(),
+        };
+        let (red, blue, bold) = ("1;31", "1;34", "1");
+        let mut out = format!(
+            "{}{}\n",
+            paint(red, &format!("error[{}]", self.code)),
+            paint(bold, &format!(": {}", self.message)),
+        );
+        let span = match self.span {
+            Some(span) => span,
+            None => {
+                let _ = writeln!(out, "{} {}", paint(blue, "-->"), file);
+                self.render_notes(&mut out, "", &paint);
+                return out;
+            }
+        };
+        let start = span.start;
+        let line_number = start.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let _ = writeln!(
+            out,
+            "{}{} {}:{}:{}",
+            gutter,
+            paint(blue, "-->"),
+            file,
+            start.line,
+            start.column
+        );
+        if let Some(line) = source.lines().nth(start.line.saturating_sub(1)) {
+            let indent: String = line
+                .chars()
+                .take(start.column.saturating_sub(1))
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let width = match span.end.line == start.line {
+                true => span.end.column.saturating_sub(start.column),
+                false => (line.chars().count() + 1).saturating_sub(start.column),
+            };
+            let mut underline = "^".repeat(width.max(1));
+            if let Some(label) = &self.label {
+                underline = format!("{} {}", underline, label);
+            }
+            let bar = paint(blue, "|");
+            let _ = writeln!(out, "{} {}", gutter, bar);
+            let _ = writeln!(out, "{} {} {}", paint(blue, &line_number), bar, line);
+            let _ = writeln!(
+                out,
+                "{} {} {}{}",
+                gutter,
+                bar,
+                indent,
+                paint(red, &underline)
+            );
+        }
+        self.render_notes(&mut out, &gutter, &paint);
+        out
+    }
+
+    fn render_notes(&self, out: &mut String, gutter: &str, paint: &dyn Fn(&str, &str) -> String) {
+        let notes = self.notes.iter().map(|note| ("note", note));
+        for (kind, text) in notes.chain(self.help.iter().map(|help| ("help", help))) {
+            let _ = writeln!(out, "{} {} {}: {}", gutter, paint("1;34", "="), kind, text);
+        }
+    }
+}
+
+impl From<&Error> for Diagnostic {
+    fn from(error: &Error) -> Self {
+        let mut diagnostic = Self::new(error.code(), &error.to_string());
+        match error {
+            Error::Syntax {
+                message,
+                span,
+                label,
+                notes,
+            } => {
+                diagnostic.message = message.clone();
+                diagnostic.span = Some(*span);
+                diagnostic.label = Some(label.clone());
+                diagnostic.notes = notes.clone();
+            }
+            Error::Runtime(e) => {
+                diagnostic.span = e.span;
+                diagnostic.notes = trace_notes(&e.trace);
+                diagnostic.help = e.help.as_deref().or(e.kind.help()).map(String::from);
+            }
+            Error::Io { .. } | Error::Many(_) => {}
+        }
+        diagnostic
+    }
+}
+
+/// A note for each Frame, a run of calls made over and over, like two
+/// Functions calling each other, is shown once with how often it repeated.
+fn trace_notes(trace: &[Frame]) -> Vec<String> {
+    let same = |a: &Frame, b: &Frame| a.name == b.name && a.span == b.span;
+    // How many more times the `len` Frames from `start` repeat right after.
+    let repeats = |start: usize, len: usize| {
+        (1..)
+            .take_while(|n| {
+                start + (n + 1) * len <= trace.len()
+                    && (0..len).all(|i| same(&trace[start + i], &trace[start + n * len + i]))
+            })
+            .count()
+    };
+    let mut notes = Vec::new();
+    let mut start = 0;
+    while start < trace.len() {
+        match (2..=4).find_map(|len| Some((len, repeats(start, len))).filter(|(_, n)| *n > 1)) {
+            Some((len, n)) => {
+                notes.extend(trace[start..start + len].iter().map(frame_note));
+                notes.push(format!("the {} calls above repeated {} more times", len, n));
+                start += len * (n + 1);
+            }
+            None => {
+                notes.push(frame_note(&trace[start]));
+                start += 1;
+            }
+        }
+    }
+    notes
+}
+
+// in `name` with 1 argument, called at 2:5 (recursed 9 more times)
+fn frame_note(frame: &Frame) -> String {
+    let mut note = format!(
+        "in `{}` with {} argument{}",
+        frame.name,
+        frame.args,
+        if frame.args == 1 { "" } else { "s" }
+    );
+    if !frame.span.is_empty() {
+        let start = frame.span.start;
+        let _ = write!(note, ", called at {}:{}", start.line, start.column);
+    }
+    if frame.repeats > 0 {
+        let _ = write!(note, " (recursed {} more times)", frame.repeats);
+    }
+    note
+}
+
+/// True when Diagnostics written to stderr should be coloured.
+pub fn use_color() -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && std::io::stderr().is_terminal()
+}
+
+#[test]
+fn render_runtime_error() {
+    use crate::parser::parser;
+    let source = "x = 1\nx + \"one\"\n";
+    let (_, exprs) = parser(source).unwrap();
+    let error = crate::interpreter::interpreter(exprs, Default::default()).unwrap_err();
+    assert_eq!(
+        Diagnostic::from(&error).render("test.uln", source, false),
+        format!(
+            "error[E0202]: {}\n --> test.uln:2:1\n  |\n2 | x + \"one\"\n  | ^^^^^^^^^\n",
+            error
+        )
+    );
+}
+
+#[test]
+fn render_parse_error() {
+    let source = "x = [1, 2";
+    use nom::error::{VerboseError, VerboseErrorKind};
+    let error = nom::Err::Error(VerboseError {
+        errors: vec![(&source[9..], VerboseErrorKind::Char(']'))],
+    });
+    let diagnostic = Diagnostic::from(&Error::syntax(source, &error));
+    assert_eq!(
+        diagnostic.render("<repl>", source, false),
+        "error[E0101]: syntax error\n --> <repl>:1:10\n  |\n1 | x = [1, 2\n  |          ^ expected `]`\n"
+    );
+    assert!(diagnostic
+        .render("<repl>", source, true)
+        .starts_with("\x1b[1;31merror[E0101]\x1b[0m"));
+}
+
+#[test]
+fn repeated_calls_are_shown_once() {
+    let frame = |name: &str| Frame::new(name, Span::default(), 1);
+    let mut trace = Vec::new();
+    for _ in 0..4 {
+        trace.extend([frame("even"), frame("odd")]);
+    }
+    trace.push(frame("main"));
+    assert_eq!(
+        trace_notes(&trace),
+        vec![
+            "in `even` with 1 argument",
+            "in `odd` with 1 argument",
+            "the 2 calls above repeated 3 more times",
+            "in `main` with 1 argument",
+        ]
+    );
+}
@@ -1,40 +1,219 @@
-use nom::error::ErrorKind;
-use nom::error::ParseError;
-use nom::Err::Error;
-use nom::IResult;
+/*
+ * error.rs
+ *
+ * The Error for everything that can go wrong running a program, from
+ * reading the file to parsing and evaluating it.  Callers decide how to
+ * show it, see `diagnostic.rs`.
+ */
+use crate::interpreter::RuntimeError;
+use crate::parser::{Expr, ExprKind, Position, Span};
+use nom::error::{VerboseError, VerboseErrorKind};
+use std::fmt;
 
-#[derive(Debug, PartialEq)]
-pub enum CustomError<I> {
-    MyError,
-    Nom(I, ErrorKind),
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// Source the parser could not read.
+    Syntax {
+        message: String,
+        span: Span,
+        // What was expected at `span`.
+        label: String,
+        // The construct the parser was reading, if any.
+        notes: Vec<String>,
+    },
+    /// An Error from evaluating the program.
+    Runtime(RuntimeError),
+    /// A file that could not be read.
+    Io { path: String, message: String },
+    /// Every syntax error in a program, the parser keeps going after one.
+    Many(Vec<Error>),
 }
 
-impl<I> ParseError<I> for CustomError<I> {
-    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
-        CustomError::Nom(input, kind)
+impl Error {
+    /// An Error for the parse error `error` in `source`.
+    pub fn syntax(source: &str, error: &nom::Err<VerboseError<&str>>) -> Self {
+        let error = match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            nom::Err::Incomplete(_) => {
+                let end = Position::new(1, 1, 0).advance(source);
+                return Self::Syntax {
+                    message: "syntax error".into(),
+                    span: Span::new(end, end),
+                    label: "unexpected end of input".into(),
+                    notes: Vec::new(),
+                };
+            }
+        };
+        // The first error is where parsing stopped, the rest are the rules it was in.
+        let (start, next, label) = match error.errors.first() {
+            Some((input, kind)) => {
+                let start = Position::new(1, 1, 0).advance(&source[..offset(source, input)]);
+                let next = input.chars().next().map(String::from).unwrap_or_default();
+                let label = label(kind, &next);
+                (start, next, label)
+            }
+            None => (Position::new(1, 1, 0), String::new(), String::new()),
+        };
+        Self::Syntax {
+            message: "syntax error".into(),
+            span: Span::new(start, start.advance(&next)),
+            label,
+            notes: notes(error.errors.iter().skip(1).map(|(_, kind)| kind)),
+        }
+    }
+
+    /// The Errors to report, one for each syntax error in a program.
+    pub fn all(&self) -> &[Error] {
+        match self {
+            Self::Many(errors) => errors,
+            error => std::slice::from_ref(error),
+        }
+    }
+
+    pub fn io(path: &str, error: &std::io::Error) -> Self {
+        Self::Io {
+            path: path.into(),
+            message: error.to_string(),
+        }
+    }
+
+    /// A code that stays the same between releases so Errors can be looked up.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Syntax { .. } => "E0101",
+            Self::Runtime(e) => e.kind.code(),
+            Self::Io { .. } => "E0301",
+            Self::Many(errors) => errors.first().map_or("E0101", Error::code),
+        }
+    }
+
+    /// The exit code for a program that stopped with this Error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Syntax { .. } => 65,
+            Self::Runtime(_) => 70,
+            Self::Io { .. } => 74,
+            Self::Many(errors) => errors.first().map_or(65, Error::exit_code),
+        }
+    }
+}
+
+impl From<RuntimeError> for Error {
+    fn from(e: RuntimeError) -> Self {
+        Self::Runtime(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax { message, label, .. } => write!(f, "{}, {}", message, label),
+            Self::Runtime(e) => write!(f, "{}", e.msg),
+            Self::Io { path, message } => write!(f, "Failed to read {}: {}", path, message),
+            Self::Many(errors) => write!(f, "{} syntax errors", errors.len()),
+        }
     }
+}
 
-    fn append(_: I, _: ErrorKind, other: Self) -> Self {
-        other
+/// The syntax errors the parser recovered from in `exprs`, or `exprs` when
+/// there are none.
+pub fn parsed(exprs: Vec<Expr>) -> Result<Vec<Expr>, Error> {
+    let mut errors: Vec<_> = exprs
+        .iter()
+        .filter_map(|expr| match &expr.kind {
+            ExprKind::Error { label, notes } => Some(Error::Syntax {
+                message: "syntax error".into(),
+                span: expr.span,
+                label: label.clone(),
+                notes: notes.clone(),
+            }),
+            _ => None,
+        })
+        .collect();
+    match errors.len() {
+        0 => Ok(exprs),
+        1 => Err(errors.remove(0)),
+        _ => Err(Error::Many(errors)),
     }
 }
 
-fn parse(input: &str) -> IResult<&str, &str, CustomError<&str>> {
-    Err(Error(CustomError::MyError))
+/// What the parser expected when it stopped with `kind` before `next`.
+pub(crate) fn label(kind: &VerboseErrorKind, next: &str) -> String {
+    match kind {
+        VerboseErrorKind::Char(c) => format!("expected `{}`", c),
+        VerboseErrorKind::Context(context) => context.to_string(),
+        VerboseErrorKind::Nom(_) if next.is_empty() => "unexpected end of input".into(),
+        VerboseErrorKind::Nom(_) => format!("unexpected `{}`", next),
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::parse;
-    use super::CustomError;
-    use nom::Err::Error;
+/// The innermost construct the parser was reading, the other contexts are
+/// grammar rules like "Term Parser" that mean nothing to the user.
+pub(crate) fn notes<'a>(mut kinds: impl Iterator<Item = &'a VerboseErrorKind>) -> Vec<String> {
+    kinds
+        .find_map(|kind| match kind {
+            VerboseErrorKind::Context("List Parser") => Some("a list"),
+            VerboseErrorKind::Context("Block Parser") => Some("a block"),
+            _ => None,
+        })
+        .map(|construct| format!("while reading {}", construct))
+        .into_iter()
+        .collect()
+}
 
-    #[test]
-    fn it_works() {
-        let err = parse("").unwrap_err();
-        match err {
-            Error(e) => assert_eq!(e, CustomError::MyError),
-            _ => panic!("Unexpected error: {:?}", err),
+/// Where `input`, a slice of `source`, starts in `source`.
+fn offset(source: &str, input: &str) -> usize {
+    (input.as_ptr() as usize)
+        .checked_sub(source.as_ptr() as usize)
+        .filter(|offset| *offset <= source.len())
+        .unwrap_or(source.len())
+}
+
+#[test]
+fn syntax_error_position() {
+    let source = "x = [1,\n 2";
+    let error = nom::Err::Error(VerboseError {
+        errors: vec![
+            (&source[10..], VerboseErrorKind::Char(']')),
+            (&source[4..], VerboseErrorKind::Context("List Parser")),
+            (&source[4..], VerboseErrorKind::Context("Unary Parser")),
+            (&source[4..], VerboseErrorKind::Context("Term Parser")),
+            (&source[0..], VerboseErrorKind::Context("Block Parser")),
+        ],
+    });
+    match Error::syntax(source, &error) {
+        Error::Syntax {
+            span, label, notes, ..
+        } => {
+            assert_eq!(span.start, Position::new(2, 3, 10));
+            assert_eq!(label, "expected `]`");
+            assert_eq!(notes, vec!["while reading a list".to_string()]);
         }
+        e => panic!("expected Syntax found {:?}", e),
     }
 }
+
+#[test]
+fn grammar_rules_are_not_notes() {
+    let (_, exprs) = crate::parser::parser("b = (2\n").unwrap();
+    match parsed(exprs).unwrap_err() {
+        Error::Syntax { notes, .. } => assert!(notes.is_empty(), "{:?}", notes),
+        e => panic!("expected Syntax found {:?}", e),
+    }
+}
+
+#[test]
+fn every_syntax_error_is_reported() {
+    let (_, exprs) = crate::parser::parser("x = )\ny = 2\nz = ]\n").unwrap();
+    let error = parsed(exprs).unwrap_err();
+    let lines: Vec<_> = error
+        .all()
+        .iter()
+        .map(|e| match e {
+            Error::Syntax { span, .. } => span.start.line,
+            e => panic!("expected Syntax found {:?}", e),
+        })
+        .collect();
+    assert_eq!(lines, vec![1, 3]);
+    assert_eq!(error.exit_code(), 65);
+}
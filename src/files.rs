@@ -1,29 +1,193 @@
-use crate::interpreter::interpreter;
-use crate::parser;
-pub fn run(path: &str) {
-    let file = std::fs::read_to_string(path);
-    match file {
-        Ok(doc) => run_code(&doc),
-        Err(_) => {
-            println!("Failed to read file.");
-            std::process::exit(74);
-        }
+use crate::diagnostic::{use_color, Diagnostic};
+use crate::error::{self, Error};
+use crate::interpreter::{interpreter, DataStruct, Environment, ErrorKind, RuntimeError};
+use crate::parser::{self, Constant, Expr, ExprKind};
+use std::io::{BufRead, Write};
+
+/// Runs the file at `path` with `args` bound as a List of Strings.
+pub fn run(path: &str, args: &[String], env: Environment) {
+    let env = with_args(args, env);
+    let code = match std::fs::read_to_string(path) {
+        Ok(doc) => run_code(path, &doc, env).unwrap_or_else(|e| fail(&e, path, &doc)),
+        Err(e) => fail(&Error::io(path, &e), path, ""),
     };
+    let _ = std::io::stdout().flush();
+    std::process::exit(code);
+}
+
+/// `env` with `args` bound as a List of Strings.
+fn with_args(args: &[String], mut env: Environment) -> Environment {
+    let args = args.iter().cloned().map(Constant::String).collect();
+    env.insert("args".into(), injected("args", Constant::List(args)));
+    env
+}
+
+/// A name the runner binds, as a Prelude so a program can define its own.
+fn injected(name: &str, value: Constant) -> DataStruct {
+    DataStruct::Prelude(name.into(), Vec::new(), value.into())
+}
+
+/// Prints a value the way `-p` and the Repl show results.
+pub fn print_value(value: &Constant) {
+    println!("{}", value);
 }
 
-fn run_code(block: &str) {
+/// Writes `error` in `source` from `file` to stderr.
+pub fn report(error: &Error, file: &str, source: &str) {
+    for error in error.all() {
+        eprint!(
+            "{}",
+            Diagnostic::from(error).render(file, source, use_color())
+        );
+    }
+}
+
+/// Reports `error` returning the exit code for it.
+fn fail(error: &Error, file: &str, source: &str) -> i32 {
+    report(error, file, source);
+    error.exit_code()
+}
+
+fn run_code(path: &str, block: &str, env: Environment) -> Result<i32, Error> {
     if block.is_empty() {
-        return;
+        return Ok(0);
     }
-    match parser::parser(block) {
-        Ok((input, expr)) => {
-            for cons in interpreter(expr) {
-                if !input.is_empty() {
-                    println!("[OUT]: {:?}", cons);
-                    println!("[LEFTOVER]: {:?}", input);
-                }
+    let (_, expr) = parser::parser(block).map_err(|e| Error::syntax(block, &e))?;
+    let (_, env) = interpreter(error::parsed(expr)?, env)?;
+    run_main(env, path, block)
+}
+
+/// Calls `main` or `main args` when the program defines it.  An Int result
+/// from 0 to 255 is used as the exit code and an `Err` result is reported
+/// and exits with 1.
+fn run_main(env: Environment, file: &str, source: &str) -> Result<i32, Error> {
+    let param_count = match env.get("main") {
+        Some(DataStruct::Function(_, param, _)) => param.len(),
+        Some(DataStruct::Value(..)) => 0,
+        _ => return Ok(0),
+    };
+    let keyword = |name: &str| Box::new(Expr::from(Constant::Keyword(name.into())));
+    let args = match param_count {
+        0 => vec![],
+        1 => vec![Box::new(ExprKind::Call(keyword("args"), vec![]).into())],
+        n => {
+            let msg = format!("main takes no parameters or just `args` but it takes {}", n);
+            let e = RuntimeError::new(&msg, ErrorKind::MisMatchedPramas);
+            return Err(e.into());
+        }
+    };
+    let (constants, _) = interpreter(vec![ExprKind::Call(keyword("main"), args).into()], env)?;
+    match constants.last() {
+        Some(Constant::Int(code)) => match u8::try_from(*code) {
+            Ok(code) => Ok(code.into()),
+            Err(_) => {
+                let msg = format!(
+                    "main returned {} which is not an exit code from 0 to 255",
+                    code
+                );
+                let e = RuntimeError::new(&msg, ErrorKind::ValueError);
+                Err(e.into())
             }
+        },
+        Some(Constant::Err(e)) => {
+            let msg = format!("main returned Err {}", e);
+            let e = RuntimeError::new(&msg, ErrorKind::ValueError);
+            report(&e.into(), file, source);
+            Ok(1)
         }
-        Err(e) => println!("[ERROR]: {}", e),
+        _ => Ok(0),
     }
 }
+
+/// Evaluates `code` once for every line on stdin with `line` bound to the
+/// line, printing the result of each line when `print` is set.
+pub fn run_lines(code: &str, print: bool, env: Environment) {
+    let result = each_line(code, env, std::io::stdin().lock(), |value| {
+        if let (true, Some(value)) = (print, value) {
+            print_value(value);
+        }
+    });
+    match result {
+        Ok(()) => {}
+        Err(e @ Error::Io { .. }) => std::process::exit(fail(&e, "<stdin>", "")),
+        Err(e) => std::process::exit(fail(&e, "<expression>", code)),
+    }
+}
+
+/// Evaluates `code` for every line of `input`, passing the last value of
+/// each to `each`, and stops at the first Error.
+fn each_line(
+    code: &str,
+    env: Environment,
+    input: impl BufRead,
+    mut each: impl FnMut(Option<&Constant>),
+) -> Result<(), Error> {
+    let (_, exprs) = parser::parser(code).map_err(|e| Error::syntax(code, &e))?;
+    let exprs = error::parsed(exprs)?;
+    for line in input.lines() {
+        let line = line.map_err(|e| Error::io("<stdin>", &e))?;
+        let mut line_env = env.clone();
+        line_env.insert("line".into(), injected("line", Constant::String(line)));
+        let (constants, _) = interpreter(exprs.clone(), line_env)?;
+        each(constants.last());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+fn run_source(source: &str, args: &[&str]) -> Result<i32, Error> {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    run_code("<test>", source, with_args(&args, Environment::new()))
+}
+
+#[test]
+fn main_result_is_the_exit_code() {
+    assert_eq!(run_source("x = 1\n", &[]).unwrap(), 0);
+    assert_eq!(run_source("main = 3\n", &[]).unwrap(), 3);
+    assert_eq!(run_source("main = \"done\"\n", &[]).unwrap(), 0);
+    for code in ["256", "(0 - 1)"] {
+        let source = format!("main = {}\n", code);
+        assert!(run_source(&source, &[]).is_err(), "{}", code);
+    }
+}
+
+#[test]
+fn main_err_is_reported_and_exits_1() {
+    assert_eq!(run_source("main = json_parse \"[\"\n", &[]).unwrap(), 1);
+}
+
+#[test]
+fn main_takes_no_parameters_or_args() {
+    assert_eq!(
+        run_source("main args = len args\n", &["a", "b"]).unwrap(),
+        2
+    );
+    assert_eq!(run_source("args = 7\nmain = args\n", &["a"]).unwrap(), 7);
+    match run_source("main a b = 0\n", &[]) {
+        Err(Error::Runtime(e)) => assert!(matches!(e.kind, ErrorKind::MisMatchedPramas)),
+        result => panic!("expected a Runtime Error found {:?}", result),
+    }
+}
+
+#[test]
+fn code_runs_for_each_line() {
+    let mut values = Vec::new();
+    let input = "a\nbc\n\n".as_bytes();
+    each_line("len line", Environment::new(), input, |value| {
+        values.push(value.cloned())
+    })
+    .unwrap();
+    let lengths = [1, 2, 0].map(|n| Some(Constant::Int(n)));
+    assert_eq!(values, lengths);
+    values.clear();
+    each_line(
+        "line = 4\nline",
+        Environment::new(),
+        "a\n".as_bytes(),
+        |value| values.push(value.cloned()),
+    )
+    .unwrap();
+    assert_eq!(values, [Some(Constant::Int(4))]);
+    assert!(each_line("len 1", Environment::new(), "a\n".as_bytes(), |_| {}).is_err());
+    assert!(each_line("x = )", Environment::new(), "a\n".as_bytes(), |_| {}).is_err());
+}
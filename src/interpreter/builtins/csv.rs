@@ -0,0 +1,288 @@
+/*
+ * builtins/csv.rs
+ *
+ * CSV Functions following RFC 4180.  Every cell is read as a String.  Records
+ * keep their keys sorted, so `csv_write_header` takes the column order.
+ */
+use super::permissions::{check_read, check_write};
+use super::{
+    list, string, to_result, type_error, Environment, Error, ErrorKind, NativeResult, Registry,
+};
+use crate::parser::Constant;
+use std::collections::BTreeMap;
+use std::fs;
+
+pub fn register(registry: &mut Registry) {
+    registry.register("csv_read", 1, csv_read);
+    registry.register("csv_read_rows", 1, csv_read_rows);
+    registry.register("csv_write", 2, csv_write);
+    registry.register("csv_write_header", 3, csv_write_header);
+}
+
+// csv_read path  → Ok([record, ...]) keyed by the header row.
+fn csv_read(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("csv_read", &args[0])?;
+    check_read("csv_read", path)?;
+    Ok(to_result(
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| parse(&text))
+            .and_then(records),
+    ))
+}
+
+// csv_read_rows path  → Ok([[cell, ...], ...]) including the header row.
+fn csv_read_rows(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("csv_read_rows", &args[0])?;
+    check_read("csv_read_rows", path)?;
+    Ok(to_result(
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| parse(&text))
+            .map(|rows| {
+                Constant::List(
+                    rows.into_iter()
+                        .map(|row| Constant::List(row.into_iter().map(Constant::String).collect()))
+                        .collect(),
+                )
+            }),
+    ))
+}
+
+// csv_write path rows  → Ok(rows written)
+// Rows are Lists, or Records written under a header of the first Record's keys.
+fn csv_write(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("csv_write", &args[0])?;
+    check_write("csv_write", path)?;
+    let rows = list("csv_write", &args[1])?;
+    let text = match rows.first() {
+        Some(Constant::Record(first)) => {
+            let header: Vec<&str> = first.keys().map(String::as_str).collect();
+            write_records("csv_write", &header, rows)?
+        }
+        _ => {
+            let mut text = String::new();
+            for row in rows {
+                let cells = list("csv_write", row)?
+                    .iter()
+                    .map(cell)
+                    .collect::<Result<Vec<_>, _>>()?;
+                write_row(&mut text, cells.iter().map(String::as_str));
+            }
+            text
+        }
+    };
+    Ok(to_result(
+        fs::write(path, text).map(|_| Constant::Int(rows.len() as i128)),
+    ))
+}
+
+// csv_write_header path header records  → Ok(records written)
+// The columns are in the order of `header`, e.g. the first row of `csv_read_rows`.
+fn csv_write_header(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("csv_write_header", &args[0])?;
+    check_write("csv_write_header", path)?;
+    let header = list("csv_write_header", &args[1])?
+        .iter()
+        .map(|name| string("csv_write_header", name))
+        .collect::<Result<Vec<_>, _>>()?;
+    let rows = list("csv_write_header", &args[2])?;
+    let text = write_records("csv_write_header", &header, rows)?;
+    Ok(to_result(
+        fs::write(path, text).map(|_| Constant::Int(rows.len() as i128)),
+    ))
+}
+
+/// The header row then a row for each Record, every Record has to have
+/// exactly the keys in `header`.
+fn write_records(name: &str, header: &[&str], rows: &[Constant]) -> Result<String, Error> {
+    let mut text = String::new();
+    write_row(&mut text, header.iter().copied());
+    for (i, row) in rows.iter().enumerate() {
+        let record = match row {
+            Constant::Record(record) => record,
+            c => return Err(type_error(name, "Record", c)),
+        };
+        let unknown = record.keys().find(|key| !header.contains(&key.as_str()));
+        let missing = header.iter().find(|key| !record.contains_key(**key));
+        if let Some(key) = unknown.map(String::as_str).or(missing.copied()) {
+            return Err(Error::new(
+                &format!(
+                    "{} Record {} does not match the header at key {:?}",
+                    name,
+                    i + 1,
+                    key
+                ),
+                ErrorKind::ValueError,
+            ));
+        }
+        let cells = header
+            .iter()
+            .map(|key| cell(&record[*key]))
+            .collect::<Result<Vec<_>, _>>()?;
+        write_row(&mut text, cells.iter().map(String::as_str));
+    }
+    Ok(text)
+}
+
+fn cell(constant: &Constant) -> Result<String, Error> {
+    match constant {
+        Constant::Null => Ok(String::new()),
+        Constant::String(_) | Constant::Int(_) | Constant::Float(_) | Constant::Boolean(_) => {
+            Ok(constant.to_string())
+        }
+        c => Err(type_error("csv_write", "String, Int, Float or Boolean", c)),
+    }
+}
+
+fn write_row<'a>(text: &mut String, cells: impl Iterator<Item = &'a str>) {
+    for (i, cell) in cells.enumerate() {
+        if i != 0 {
+            text.push(',');
+        }
+        if cell.contains([',', '"', '\r', '\n']) {
+            text.push('"');
+            text.push_str(&cell.replace('"', "\"\""));
+            text.push('"');
+        } else {
+            text.push_str(cell);
+        }
+    }
+    text.push_str("\r\n");
+}
+
+fn records(rows: Vec<Vec<String>>) -> Result<Constant, String> {
+    let mut rows = rows.into_iter();
+    let header = rows.next().unwrap_or_default();
+    let mut records = Vec::new();
+    for (line, row) in rows.enumerate() {
+        if row.len() != header.len() {
+            return Err(format!(
+                "Row {} has {} fields but the header has {}",
+                line + 2,
+                row.len(),
+                header.len()
+            ));
+        }
+        let record: BTreeMap<String, Constant> = header
+            .iter()
+            .cloned()
+            .zip(row.into_iter().map(Constant::String))
+            .collect();
+        records.push(Constant::Record(record));
+    }
+    Ok(Constant::List(records))
+}
+
+/// Splits CSV text into rows of cells, a trailing newline does not add a row.
+fn parse(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut chars = text.chars().peekable();
+    let mut quoted = false;
+    let mut line = 1;
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            (true, '"') => match chars.peek() {
+                None | Some(',') | Some('\r') | Some('\n') => quoted = false,
+                Some(c) => {
+                    return Err(format!("Unexpected {:?} after a quote on line {}", c, line))
+                }
+            },
+            (true, c) => {
+                if c == '\n' {
+                    line += 1;
+                }
+                cell.push(c);
+            }
+            (false, '"') if cell.is_empty() => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut cell)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+                line += 1;
+            }
+            (false, c) => cell.push(c),
+        }
+    }
+    if quoted {
+        return Err(format!("Unclosed quote on line {}", line));
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[test]
+fn csv_parse_rfc4180() {
+    assert_eq!(
+        parse("name,note\r\n\"Doe, J\",\"said \"\"hi\"\"\nthen left\"\r\nx,\n").unwrap(),
+        vec![
+            vec!["name".to_string(), "note".to_string()],
+            vec!["Doe, J".to_string(), "said \"hi\"\nthen left".to_string()],
+            vec!["x".to_string(), "".to_string()],
+        ]
+    );
+    assert!(parse("\"open").is_err());
+    assert!(parse("\"a\"b").is_err());
+}
+
+#[test]
+fn csv_write_quotes() {
+    let mut text = String::new();
+    write_row(&mut text, ["a,b", "c\"d", "e"].into_iter());
+    assert_eq!(text, "\"a,b\",\"c\"\"d\",e\r\n");
+    assert_eq!(parse(&text).unwrap(), vec![vec!["a,b", "c\"d", "e"]]);
+}
+
+#[test]
+fn csv_records_follow_the_header() {
+    let record = |pairs: &[(&str, &str)]| {
+        Constant::Record(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), Constant::String(v.to_string())))
+                .collect(),
+        )
+    };
+    let rows = [record(&[("b", "1"), ("a", "2")])];
+    assert_eq!(
+        write_records("csv_write_header", &["b", "a"], &rows).unwrap(),
+        "b,a\r\n1,2\r\n"
+    );
+    assert!(write_records("csv_write_header", &["b"], &rows).is_err());
+    assert!(write_records("csv_write_header", &["b", "a", "c"], &rows).is_err());
+    let rows = [record(&[("a", "1")]), record(&[("a", "2"), ("b", "3")])];
+    assert!(write_records("csv_write", &["a"], &rows).is_err());
+}
+
+#[test]
+fn csv_round_trip_keeps_the_columns() {
+    super::set_permissions(super::Permissions::all());
+    let env = Environment::new();
+    let file = std::env::temp_dir().join(format!("uln_csv_test_{}", std::process::id()));
+    let text = "name,age,city\r\nAda,36,\"London, UK\"\r\nAlan,41,Wilmslow\r\n";
+    fs::write(&file, text).unwrap();
+    let path = [Constant::String(file.to_string_lossy().into_owned())];
+    let ok = |result: NativeResult| match result.unwrap() {
+        Constant::Ok(value) => *value,
+        c => panic!("expected Ok found {}", c),
+    };
+    let records = ok(csv_read(&path, &env));
+    let header = match ok(csv_read_rows(&path, &env)) {
+        Constant::List(rows) => rows[0].clone(),
+        c => panic!("expected List found {}", c),
+    };
+    ok(csv_write_header(&[path[0].clone(), header, records], &env));
+    let written = fs::read_to_string(&file).unwrap();
+    fs::remove_file(&file).unwrap();
+    assert_eq!(written, text);
+}
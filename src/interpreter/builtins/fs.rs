@@ -0,0 +1,126 @@
+/*
+ * builtins/fs.rs
+ *
+ * File System Functions.  Anything that can fail returns `Ok` or `Err`,
+ * paths outside of `--allow-read` and `--allow-write` are PermissionDenied.
+ */
+use super::permissions::{check_read, check_write};
+use super::{string, to_result, Environment, NativeResult, Registry};
+use crate::parser::Constant;
+use std::fs;
+use std::io::Write;
+
+pub fn register(registry: &mut Registry) {
+    registry.register("read_file", 1, read_file);
+    registry.register("write_file", 2, write_file);
+    registry.register("append_file", 2, append_file);
+    registry.register("read_lines", 1, read_lines);
+    registry.register("list_dir", 1, list_dir);
+    registry.register("exists", 1, exists);
+    registry.register("remove_file", 1, remove_file);
+}
+
+// read_file path  → Ok(contents)
+fn read_file(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("read_file", &args[0])?;
+    check_read("read_file", path)?;
+    Ok(to_result(fs::read_to_string(path).map(Constant::String)))
+}
+
+// write_file path contents  → Ok(bytes written)
+fn write_file(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("write_file", &args[0])?;
+    check_write("write_file", path)?;
+    let contents = string("write_file", &args[1])?;
+    Ok(to_result(
+        fs::write(path, contents).map(|_| Constant::Int(contents.len() as i128)),
+    ))
+}
+
+// append_file path contents  → Ok(bytes written)
+fn append_file(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("append_file", &args[0])?;
+    check_write("append_file", path)?;
+    let contents = string("append_file", &args[1])?;
+    Ok(to_result(
+        fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(contents.as_bytes()))
+            .map(|_| Constant::Int(contents.len() as i128)),
+    ))
+}
+
+// read_lines path  → Ok([line, ...])
+fn read_lines(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("read_lines", &args[0])?;
+    check_read("read_lines", path)?;
+    Ok(to_result(fs::read_to_string(path).map(|contents| {
+        Constant::List(
+            contents
+                .lines()
+                .map(|line| Constant::String(line.into()))
+                .collect(),
+        )
+    })))
+}
+
+// list_dir path  → Ok([name, ...]) sorted by name.
+fn list_dir(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("list_dir", &args[0])?;
+    check_read("list_dir", path)?;
+    Ok(to_result(fs::read_dir(path).and_then(|entries| {
+        let mut names = Vec::new();
+        for entry in entries {
+            names.push(entry?.file_name().to_string_lossy().into_owned());
+        }
+        names.sort();
+        Ok(Constant::List(
+            names.into_iter().map(Constant::String).collect(),
+        ))
+    })))
+}
+
+// exists path
+fn exists(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("exists", &args[0])?;
+    check_read("exists", path)?;
+    Ok(Constant::Boolean(std::path::Path::new(path).exists()))
+}
+
+// remove_file path  → Ok(true)
+fn remove_file(args: &[Constant], _: &Environment) -> NativeResult {
+    let path = string("remove_file", &args[0])?;
+    check_write("remove_file", path)?;
+    Ok(to_result(
+        fs::remove_file(path).map(|_| Constant::Boolean(true)),
+    ))
+}
+
+#[test]
+fn file_round_trip() {
+    super::set_permissions(super::Permissions::all());
+    let env = Environment::new();
+    let path = std::env::temp_dir().join(format!("uln_fs_test_{}", std::process::id()));
+    let path = Constant::String(path.to_string_lossy().into_owned());
+    let text = |s: &str| Constant::String(s.into());
+    assert_eq!(
+        write_file(&[path.clone(), text("a\n")], &env).unwrap(),
+        Constant::Ok(Box::new(Constant::Int(2)))
+    );
+    append_file(&[path.clone(), text("b\n")], &env).unwrap();
+    assert_eq!(
+        read_lines(std::slice::from_ref(&path), &env).unwrap(),
+        Constant::Ok(Box::new(Constant::List(vec![text("a"), text("b")])))
+    );
+    remove_file(std::slice::from_ref(&path), &env).unwrap();
+    assert_eq!(
+        exists(std::slice::from_ref(&path), &env).unwrap(),
+        Constant::Boolean(false)
+    );
+    assert!(matches!(
+        read_file(&[path], &env).unwrap(),
+        Constant::Err(_)
+    ));
+}
@@ -0,0 +1,63 @@
+/*
+ * builtins/io.rs
+ *
+ * Input and Output Functions.
+ */
+use super::{to_result, Environment, NativeResult, Registry};
+use crate::parser::Constant;
+use std::io::{self, BufRead, Read};
+
+pub fn register(registry: &mut Registry) {
+    registry.register("print", 1, print);
+    registry.register("println", 1, println);
+    registry.register("read_line", 0, read_line);
+    registry.register("read_all", 0, read_all);
+    registry.register("lines_stdin", 0, lines_stdin);
+}
+
+// print value
+fn print(args: &[Constant], _: &Environment) -> NativeResult {
+    print!("{}", args[0]);
+    Ok(args[0].clone())
+}
+
+// println value
+fn println(args: &[Constant], _: &Environment) -> NativeResult {
+    println!("{}", args[0]);
+    Ok(args[0].clone())
+}
+
+// read_line  → Ok(line) without the newline or Err at the end of input.
+fn read_line(_: &[Constant], _: &Environment) -> NativeResult {
+    let mut line = String::new();
+    Ok(to_result(match io::stdin().lock().read_line(&mut line) {
+        Ok(0) => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input")),
+        Ok(_) => Ok(Constant::String(
+            line.trim_end_matches(&['\n', '\r'][..]).into(),
+        )),
+        Err(e) => Err(e),
+    }))
+}
+
+// read_all  → Ok(everything left on stdin)
+fn read_all(_: &[Constant], _: &Environment) -> NativeResult {
+    let mut input = String::new();
+    Ok(to_result(
+        io::stdin()
+            .lock()
+            .read_to_string(&mut input)
+            .map(|_| Constant::String(input)),
+    ))
+}
+
+// lines_stdin  → Ok([line, ...]) for everything left on stdin.
+fn lines_stdin(_: &[Constant], _: &Environment) -> NativeResult {
+    Ok(to_result(
+        io::stdin()
+            .lock()
+            .lines()
+            .map(|line| line.map(Constant::String))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Constant::List),
+    ))
+}
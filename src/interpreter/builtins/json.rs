@@ -0,0 +1,309 @@
+/*
+ * builtins/json.rs
+ *
+ * JSON Functions.  Objects are Records, arrays are Lists, numbers without a
+ * fraction or exponent are Int when they fit in one and null is Null.
+ */
+use super::{string, to_result, type_error, Environment, Error, ErrorKind, NativeResult, Registry};
+use crate::parser::Constant;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1, take_while_m_n},
+    character::complete::{char, digit0, digit1, multispace0, one_of},
+    combinator::{all_consuming, map, map_opt, map_res, opt, recognize, value, verify},
+    error::{ErrorKind as NomErrorKind, ParseError},
+    multi::{fold_many0, separated_list0},
+    sequence::{delimited, pair, preceded, separated_pair, tuple},
+    IResult,
+};
+use std::fmt::Write;
+
+pub fn register(registry: &mut Registry) {
+    registry.register("json_parse", 1, json_parse);
+    registry.register("json_stringify", 1, json_stringify);
+    registry.register("json_pretty", 1, json_pretty);
+}
+
+/// How deep arrays and objects can be inside each other.
+const MAX_NESTING: usize = 128;
+
+// json_parse text  → Ok(value) or Err(message), nesting deeper than
+// MAX_NESTING is a ValueError.
+fn json_parse(args: &[Constant], _: &Environment) -> NativeResult {
+    let text = string("json_parse", &args[0])?;
+    match all_consuming(|i| json_value(i, 0))(text) {
+        Err(nom::Err::Failure(e)) if e.code == NomErrorKind::TooLarge => Err(Error::new(
+            &format!("JSON nested more than {} deep", MAX_NESTING),
+            ErrorKind::ValueError,
+        )),
+        result => Ok(to_result(
+            result
+                .map(|(_, value)| value)
+                .map_err(|e| format!("Invalid JSON: {}", e)),
+        )),
+    }
+}
+
+// json_stringify value  → compact JSON text.
+fn json_stringify(args: &[Constant], _: &Environment) -> NativeResult {
+    let mut out = String::new();
+    write_json(&mut out, &args[0], None, 0)?;
+    Ok(Constant::String(out))
+}
+
+// json_pretty value  → JSON text indented by two spaces.
+fn json_pretty(args: &[Constant], _: &Environment) -> NativeResult {
+    let mut out = String::new();
+    write_json(&mut out, &args[0], Some(2), 0)?;
+    Ok(Constant::String(out))
+}
+
+fn json_value(input: &str, depth: usize) -> IResult<&str, Constant> {
+    if depth > MAX_NESTING {
+        let error = ParseError::from_error_kind(input, NomErrorKind::TooLarge);
+        return Err(nom::Err::Failure(error));
+    }
+    let item = |i| json_value(i, depth + 1);
+    delimited(
+        multispace0,
+        alt((
+            value(Constant::Null, tag("null")),
+            value(Constant::Boolean(true), tag("true")),
+            value(Constant::Boolean(false), tag("false")),
+            map(json_string, Constant::String),
+            json_number,
+            map(
+                delimited(
+                    char('['),
+                    separated_list0(char(','), item),
+                    preceded(multispace0, char(']')),
+                ),
+                Constant::List,
+            ),
+            map(
+                delimited(
+                    char('{'),
+                    separated_list0(
+                        char(','),
+                        separated_pair(
+                            delimited(multispace0, json_string, multispace0),
+                            char(':'),
+                            item,
+                        ),
+                    ),
+                    preceded(multispace0, char('}')),
+                ),
+                |pairs| Constant::Record(pairs.into_iter().collect()),
+            ),
+        )),
+        multispace0,
+    )(input)
+}
+
+// -?(0|[1-9][0-9]*)(.[0-9]+)?([eE][+-]?[0-9]+)?
+fn json_number(input: &str) -> IResult<&str, Constant> {
+    let integer = alt((tag("0"), recognize(pair(one_of("123456789"), digit0))));
+    let fraction = pair(char('.'), digit1);
+    let exponent = tuple((one_of("eE"), opt(one_of("+-")), digit1));
+    map_res(
+        recognize(tuple((
+            opt(char('-')),
+            integer,
+            opt(fraction),
+            opt(exponent),
+        ))),
+        |number: &str| match number.parse() {
+            Ok(i) if !number.contains(['.', 'e', 'E']) => Ok(Constant::Int(i)),
+            _ => number.parse().map(Constant::Float),
+        },
+    )(input)
+}
+
+// Control characters have to be escaped.
+fn json_string(input: &str) -> IResult<&str, String> {
+    delimited(
+        char('"'),
+        fold_many0(
+            alt((
+                map(
+                    take_while1(|c: char| c != '"' && c != '\\' && c >= ' '),
+                    |s: &str| s.to_string(),
+                ),
+                map(preceded(char('\\'), json_escape), String::from),
+            )),
+            String::new,
+            |acc, fragment| acc + &fragment,
+        ),
+        char('"'),
+    )(input)
+}
+
+fn json_escape(input: &str) -> IResult<&str, char> {
+    alt((
+        value('"', char('"')),
+        value('\\', char('\\')),
+        value('/', char('/')),
+        value('\u{08}', char('b')),
+        value('\u{0C}', char('f')),
+        value('\n', char('n')),
+        value('\r', char('r')),
+        value('\t', char('t')),
+        preceded(char('u'), json_unicode),
+    ))(input)
+}
+
+// The hex digits after `\u`.  A character outside the Basic Multilingual
+// Plane is a high surrogate followed by `\u` and a low surrogate.
+fn json_unicode(input: &str) -> IResult<&str, char> {
+    let high = verify(hex, |high| (0xD800..0xDC00).contains(high));
+    let low = verify(preceded(tag("\\u"), hex), |low| {
+        (0xDC00..0xE000).contains(low)
+    });
+    alt((
+        map_opt(hex, std::char::from_u32),
+        map_opt(pair(high, low), |(high, low)| {
+            std::char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+        }),
+    ))(input)
+}
+
+fn hex(input: &str) -> IResult<&str, u32> {
+    map_res(
+        take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()),
+        |hex| u32::from_str_radix(hex, 16),
+    )(input)
+}
+
+fn write_json(
+    out: &mut String,
+    constant: &Constant,
+    indent: Option<usize>,
+    depth: usize,
+) -> Result<(), Error> {
+    // Newline and indentation before an item, nothing when compact.
+    let newline = |out: &mut String, depth: usize| {
+        if let Some(width) = indent {
+            out.push('\n');
+            out.push_str(&" ".repeat(width * depth));
+        }
+    };
+    match constant {
+        Constant::Null => out.push_str("null"),
+        Constant::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
+        Constant::Int(i) => out.push_str(&i.to_string()),
+        Constant::Float(f) if f.is_finite() => out.push_str(&format!("{:?}", f)),
+        Constant::Float(_) => out.push_str("null"),
+        Constant::String(s) => write_string(out, s),
+        Constant::List(list) if list.is_empty() => out.push_str("[]"),
+        Constant::List(list) => {
+            out.push('[');
+            for (i, item) in list.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                newline(out, depth + 1);
+                write_json(out, item, indent, depth + 1)?;
+            }
+            newline(out, depth);
+            out.push(']');
+        }
+        Constant::Record(record) if record.is_empty() => out.push_str("{}"),
+        Constant::Record(record) => {
+            out.push('{');
+            for (i, (key, item)) in record.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                newline(out, depth + 1);
+                write_string(out, key);
+                out.push_str(if indent.is_some() { ": " } else { ":" });
+                write_json(out, item, indent, depth + 1)?;
+            }
+            newline(out, depth);
+            out.push('}');
+        }
+        c => return Err(type_error("json_stringify", "JSON value", c)),
+    }
+    Ok(())
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[test]
+fn json_round_trip() {
+    let env = Environment::new();
+    let text = r#" {"name": "uln\né", "tags": [1, 2.5, true, null], "empty": {}} "#;
+    let value = match json_parse(&[Constant::String(text.into())], &env).unwrap() {
+        Constant::Ok(value) => *value,
+        c => panic!("expected Ok found {}", c),
+    };
+    assert_eq!(
+        json_stringify(std::slice::from_ref(&value), &env).unwrap(),
+        Constant::String(r#"{"empty":{},"name":"uln\né","tags":[1,2.5,true,null]}"#.into())
+    );
+    assert_eq!(
+        json_pretty(&[Constant::List(vec![Constant::Int(1)])], &env).unwrap(),
+        Constant::String("[\n  1\n]".into())
+    );
+    assert!(matches!(
+        json_parse(&[Constant::String("[1,".into())], &env).unwrap(),
+        Constant::Err(_)
+    ));
+}
+
+#[test]
+fn json_parse_edge_cases() {
+    let parse = |text: &str| json_parse(&[Constant::String(text.into())], &Environment::new());
+    let ok = |c: Constant| Constant::Ok(Box::new(c));
+    assert_eq!(
+        parse(r#""😀 é""#).unwrap(),
+        ok(Constant::String("😀 é".into()))
+    );
+    assert_eq!(
+        parse(r#""\ud83d\ude00""#).unwrap(),
+        ok(Constant::String("😀".into()))
+    );
+    assert!(matches!(parse(r#""\ud83d""#).unwrap(), Constant::Err(_)));
+    assert!(matches!(
+        parse(r#""\ud83d\u0041""#).unwrap(),
+        Constant::Err(_)
+    ));
+    assert!(matches!(parse(r#""\ude00""#).unwrap(), Constant::Err(_)));
+    for number in ["+1", ".5", "1.", "01", "1e", "-"] {
+        assert!(
+            matches!(parse(number).unwrap(), Constant::Err(_)),
+            "{}",
+            number
+        );
+    }
+    assert_eq!(parse("-0.5e1").unwrap(), ok(Constant::Float(-5.0)));
+    assert_eq!(
+        parse("1000000000000000000000000000000000000000000").unwrap(),
+        ok(Constant::Float(1e42))
+    );
+    assert!(matches!(parse("\"a\tb\"").unwrap(), Constant::Err(_)));
+    assert!(matches!(parse("\"a\nb\"").unwrap(), Constant::Err(_)));
+    let nested = |n| "[".repeat(n) + &"]".repeat(n);
+    assert!(matches!(
+        parse(&nested(MAX_NESTING)).unwrap(),
+        Constant::Ok(_)
+    ));
+    assert!(parse(&nested(MAX_NESTING + 1)).is_err());
+    assert!(parse(&nested(100_000)).is_err());
+}
@@ -0,0 +1,298 @@
+/*
+ * builtins/list.rs
+ *
+ * Higher order List Functions.
+ */
+use super::{
+    boolean, call, compare, list, overflow, type_error, Environment, NativeResult, Registry,
+};
+use crate::parser::Constant;
+use std::cmp::Ordering;
+
+pub fn register(registry: &mut Registry) {
+    registry.register("map", 2, map);
+    registry.register("filter", 2, filter);
+    registry.register("foldl", 3, foldl);
+    registry.register("foldr", 3, foldr);
+    registry.register("zip", 2, zip);
+    registry.register("zip_with", 3, zip_with);
+    registry.register("any", 2, any);
+    registry.register("all", 2, all);
+    registry.register("sort", 1, sort);
+    registry.register("sort_by", 2, sort_by);
+    registry.register("group_by", 2, group_by);
+    registry.register("unique", 1, unique);
+    registry.register("reverse", 1, reverse);
+    registry.register("len", 1, len);
+    registry.register("contains", 2, contains);
+    registry.register("sum", 1, sum);
+    registry.register("product", 1, product);
+}
+
+// map f list
+fn map(args: &[Constant], env: &Environment) -> NativeResult {
+    let mut result = Vec::new();
+    for item in list("map", &args[1])? {
+        result.push(call(&args[0], vec![item.clone()], env)?);
+    }
+    Ok(Constant::List(result))
+}
+
+// filter predicate list
+fn filter(args: &[Constant], env: &Environment) -> NativeResult {
+    let mut result = Vec::new();
+    for item in list("filter", &args[1])? {
+        if boolean("filter", &call(&args[0], vec![item.clone()], env)?)? {
+            result.push(item.clone());
+        }
+    }
+    Ok(Constant::List(result))
+}
+
+// foldl f acc list  → f (f acc x1) x2 ...
+fn foldl(args: &[Constant], env: &Environment) -> NativeResult {
+    let mut acc = args[1].clone();
+    for item in list("foldl", &args[2])? {
+        acc = call(&args[0], vec![acc, item.clone()], env)?;
+    }
+    Ok(acc)
+}
+
+// foldr f acc list  → f x1 (f x2 acc) ...
+fn foldr(args: &[Constant], env: &Environment) -> NativeResult {
+    let mut acc = args[1].clone();
+    for item in list("foldr", &args[2])?.iter().rev() {
+        acc = call(&args[0], vec![item.clone(), acc], env)?;
+    }
+    Ok(acc)
+}
+
+// zip list list  → [[x1, y1], [x2, y2], ...]
+fn zip(args: &[Constant], _: &Environment) -> NativeResult {
+    let lhs = list("zip", &args[0])?;
+    let rhs = list("zip", &args[1])?;
+    Ok(Constant::List(
+        lhs.iter()
+            .zip(rhs)
+            .map(|(l, r)| Constant::List(vec![l.clone(), r.clone()]))
+            .collect(),
+    ))
+}
+
+// zip_with f list list
+fn zip_with(args: &[Constant], env: &Environment) -> NativeResult {
+    let lhs = list("zip_with", &args[1])?;
+    let rhs = list("zip_with", &args[2])?;
+    let mut result = Vec::new();
+    for (l, r) in lhs.iter().zip(rhs) {
+        result.push(call(&args[0], vec![l.clone(), r.clone()], env)?);
+    }
+    Ok(Constant::List(result))
+}
+
+// any predicate list
+fn any(args: &[Constant], env: &Environment) -> NativeResult {
+    for item in list("any", &args[1])? {
+        if boolean("any", &call(&args[0], vec![item.clone()], env)?)? {
+            return Ok(Constant::Boolean(true));
+        }
+    }
+    Ok(Constant::Boolean(false))
+}
+
+// all predicate list
+fn all(args: &[Constant], env: &Environment) -> NativeResult {
+    for item in list("all", &args[1])? {
+        if !boolean("all", &call(&args[0], vec![item.clone()], env)?)? {
+            return Ok(Constant::Boolean(false));
+        }
+    }
+    Ok(Constant::Boolean(true))
+}
+
+// sort list
+fn sort(args: &[Constant], _: &Environment) -> NativeResult {
+    let mut keyed: Vec<(Constant, Constant)> = list("sort", &args[0])?
+        .iter()
+        .map(|item| (item.clone(), item.clone()))
+        .collect();
+    sort_keyed(&mut keyed)?;
+    Ok(Constant::List(
+        keyed.into_iter().map(|(_, item)| item).collect(),
+    ))
+}
+
+// sort_by key list  → stable sort by the result of `key item`.
+fn sort_by(args: &[Constant], env: &Environment) -> NativeResult {
+    let mut keyed = Vec::new();
+    for item in list("sort_by", &args[1])? {
+        keyed.push((call(&args[0], vec![item.clone()], env)?, item.clone()));
+    }
+    sort_keyed(&mut keyed)?;
+    Ok(Constant::List(
+        keyed.into_iter().map(|(_, item)| item).collect(),
+    ))
+}
+
+fn sort_keyed(keyed: &mut [(Constant, Constant)]) -> Result<(), super::Error> {
+    let mut error = None;
+    keyed.sort_by(|(k1, _), (k2, _)| {
+        compare(k1, k2).unwrap_or_else(|e| {
+            error.get_or_insert(e);
+            Ordering::Equal
+        })
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+// group_by key list  → groups of items with equal keys, in order of first appearance.
+fn group_by(args: &[Constant], env: &Environment) -> NativeResult {
+    let mut groups: Vec<(Constant, Vec<Constant>)> = Vec::new();
+    for item in list("group_by", &args[1])? {
+        let key = call(&args[0], vec![item.clone()], env)?;
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(item.clone()),
+            None => groups.push((key, vec![item.clone()])),
+        }
+    }
+    Ok(Constant::List(
+        groups
+            .into_iter()
+            .map(|(_, group)| Constant::List(group))
+            .collect(),
+    ))
+}
+
+// unique list  → keeps the first occurrence of each item.
+fn unique(args: &[Constant], _: &Environment) -> NativeResult {
+    let mut result: Vec<Constant> = Vec::new();
+    for item in list("unique", &args[0])? {
+        if !result.contains(item) {
+            result.push(item.clone());
+        }
+    }
+    Ok(Constant::List(result))
+}
+
+// reverse list
+// reverse string
+fn reverse(args: &[Constant], _: &Environment) -> NativeResult {
+    match &args[0] {
+        Constant::String(s) => Ok(Constant::String(s.chars().rev().collect())),
+        c => {
+            let mut result = list("reverse", c)?.to_vec();
+            result.reverse();
+            Ok(Constant::List(result))
+        }
+    }
+}
+
+// len list
+// len string  → number of chars.
+fn len(args: &[Constant], _: &Environment) -> NativeResult {
+    match &args[0] {
+        Constant::String(s) => Ok(Constant::Int(s.chars().count() as i128)),
+        c => Ok(Constant::Int(list("len", c)?.len() as i128)),
+    }
+}
+
+// contains item list
+// contains substring string
+fn contains(args: &[Constant], _: &Environment) -> NativeResult {
+    match (&args[0], &args[1]) {
+        (Constant::String(sub), Constant::String(s)) => {
+            Ok(Constant::Boolean(s.contains(sub.as_str())))
+        }
+        (item, c) => Ok(Constant::Boolean(list("contains", c)?.contains(item))),
+    }
+}
+
+// sum list
+fn sum(args: &[Constant], _: &Environment) -> NativeResult {
+    fold_numbers("sum", &args[0], 0, i128::checked_add, |f1, f2| f1 + f2)
+}
+
+// product list
+fn product(args: &[Constant], _: &Environment) -> NativeResult {
+    fold_numbers("product", &args[0], 1, i128::checked_mul, |f1, f2| f1 * f2)
+}
+
+/// Folds a List of Int and Float, staying an Int until a Float is found.
+/// An Int result that leaves the range of an Int is an Error.
+fn fold_numbers(
+    name: &str,
+    constant: &Constant,
+    init: i128,
+    int_op: fn(i128, i128) -> Option<i128>,
+    float_op: fn(f64, f64) -> f64,
+) -> NativeResult {
+    let mut acc = Constant::Int(init);
+    for item in list(name, constant)? {
+        acc = match (acc, item) {
+            (Constant::Int(i1), Constant::Int(i2)) => {
+                Constant::Int(int_op(i1, *i2).ok_or_else(|| overflow(name))?)
+            }
+            (Constant::Int(i1), Constant::Float(f2)) => Constant::Float(float_op(i1 as f64, *f2)),
+            (Constant::Float(f1), Constant::Int(i2)) => Constant::Float(float_op(f1, *i2 as f64)),
+            (Constant::Float(f1), Constant::Float(f2)) => Constant::Float(float_op(f1, *f2)),
+            (_, c) => return Err(type_error(name, "Int or Float", c)),
+        };
+    }
+    Ok(acc)
+}
+
+#[cfg(test)]
+fn ints(list: &[i128]) -> Constant {
+    Constant::List(list.iter().map(|i| Constant::Int(*i)).collect())
+}
+
+#[test]
+fn map_with_function() {
+    use crate::parser::{Expr, ExprKind, Operator};
+    let double = Constant::Function {
+        name: "double".into(),
+        params: vec!["x".into()],
+        body: Box::new(Expr::from(ExprKind::Binary {
+            op: Operator::Multiply,
+            lhs: Box::new(Expr::from(ExprKind::Call(
+                Box::new(Constant::Keyword("x".into()).into()),
+                vec![],
+            ))),
+            rhs: Box::new(Constant::Int(2).into()),
+        })),
+        applied: vec![],
+    };
+    let env = Environment::new();
+    assert_eq!(
+        map(&[double, ints(&[1, 2, 3])], &env).unwrap(),
+        ints(&[2, 4, 6])
+    );
+}
+
+#[test]
+fn list_natives() {
+    let env = Environment::new();
+    assert_eq!(sort(&[ints(&[3, 1, 2])], &env).unwrap(), ints(&[1, 2, 3]));
+    assert_eq!(
+        unique(&[ints(&[1, 2, 1, 3, 2])], &env).unwrap(),
+        ints(&[1, 2, 3])
+    );
+    assert_eq!(sum(&[ints(&[1, 2, 3])], &env).unwrap(), Constant::Int(6));
+    assert_eq!(
+        product(
+            &[Constant::List(vec![Constant::Int(2), Constant::Float(1.5)])],
+            &env
+        )
+        .unwrap(),
+        Constant::Float(3.0)
+    );
+    assert!(product(&[ints(&[i128::MAX, 2])], &env).is_err());
+    assert!(sum(&[ints(&[i128::MAX, 1])], &env).is_err());
+    assert_eq!(
+        zip(&[ints(&[1, 2]), ints(&[3, 4, 5])], &env).unwrap(),
+        Constant::List(vec![ints(&[1, 3]), ints(&[2, 4])])
+    );
+}
@@ -0,0 +1,190 @@
+/*
+ * builtins/math.rs
+ *
+ * Math Functions and Constants.  Int is promoted to Float when a Function
+ * needs one or when Int and Float are mixed.
+ */
+use super::{
+    compare, int, overflow, type_error, Environment, Error, ErrorKind, NativeResult, Registry,
+};
+use crate::parser::Constant;
+use std::cmp::Ordering;
+
+pub fn register(registry: &mut Registry) {
+    registry.register("pi", 0, |_, _| Ok(Constant::Float(std::f64::consts::PI)));
+    registry.register("e", 0, |_, _| Ok(Constant::Float(std::f64::consts::E)));
+    registry.register("sqrt", 1, |args, _| float_op("sqrt", &args[0], f64::sqrt));
+    registry.register("exp", 1, |args, _| float_op("exp", &args[0], f64::exp));
+    registry.register("ln", 1, |args, _| float_op("ln", &args[0], f64::ln));
+    registry.register("log10", 1, |args, _| {
+        float_op("log10", &args[0], f64::log10)
+    });
+    registry.register("sin", 1, |args, _| float_op("sin", &args[0], f64::sin));
+    registry.register("cos", 1, |args, _| float_op("cos", &args[0], f64::cos));
+    registry.register("tan", 1, |args, _| float_op("tan", &args[0], f64::tan));
+    registry.register("asin", 1, |args, _| float_op("asin", &args[0], f64::asin));
+    registry.register("acos", 1, |args, _| float_op("acos", &args[0], f64::acos));
+    registry.register("atan", 1, |args, _| float_op("atan", &args[0], f64::atan));
+    registry.register("floor", 1, |args, _| {
+        round_op("floor", &args[0], f64::floor)
+    });
+    registry.register("ceil", 1, |args, _| round_op("ceil", &args[0], f64::ceil));
+    registry.register("round", 1, |args, _| {
+        round_op("round", &args[0], f64::round)
+    });
+    registry.register("pow", 2, pow);
+    registry.register("abs", 1, abs);
+    registry.register("min", 2, min);
+    registry.register("max", 2, max);
+    registry.register("clamp", 3, clamp);
+    registry.register("gcd", 2, gcd);
+    registry.register("lcm", 2, lcm);
+}
+
+fn float(name: &str, constant: &Constant) -> Result<f64, Error> {
+    match constant {
+        Constant::Int(i) => Ok(*i as f64),
+        Constant::Float(f) => Ok(*f),
+        c => Err(type_error(name, "Int or Float", c)),
+    }
+}
+
+fn float_op(name: &str, constant: &Constant, op: fn(f64) -> f64) -> NativeResult {
+    Ok(Constant::Float(op(float(name, constant)?)))
+}
+
+// floor, ceil and round return an Int, NaN, infinity and Floats too big
+// for an Int have none.
+fn round_op(name: &str, constant: &Constant, op: fn(f64) -> f64) -> NativeResult {
+    let f = match constant {
+        Constant::Int(i) => return Ok(Constant::Int(*i)),
+        c => op(float(name, c)?),
+    };
+    let limit = 2f64.powi(127);
+    match f.is_finite() && (-limit..limit).contains(&f) {
+        true => Ok(Constant::Int(f as i128)),
+        false => Err(Error::new(
+            &format!("{} of {} is not an Int", name, f),
+            ErrorKind::ValueError,
+        )),
+    }
+}
+
+// pow base exponent  → Int when both are Int and the exponent is not negative.
+fn pow(args: &[Constant], _: &Environment) -> NativeResult {
+    if let (Constant::Int(base), Constant::Int(exp)) = (&args[0], &args[1]) {
+        if let Some(result) = u32::try_from(*exp).ok().and_then(|e| base.checked_pow(e)) {
+            return Ok(Constant::Int(result));
+        }
+    }
+    let base = float("pow", &args[0])?;
+    Ok(Constant::Float(base.powf(float("pow", &args[1])?)))
+}
+
+// abs number
+fn abs(args: &[Constant], _: &Environment) -> NativeResult {
+    match &args[0] {
+        Constant::Int(i) => i
+            .checked_abs()
+            .map(Constant::Int)
+            .ok_or_else(|| overflow("abs")),
+        c => float_op("abs", c, f64::abs),
+    }
+}
+
+// min number number
+fn min(args: &[Constant], _: &Environment) -> NativeResult {
+    pick("min", &args[0], &args[1], Ordering::Less)
+}
+
+// max number number
+fn max(args: &[Constant], _: &Environment) -> NativeResult {
+    pick("max", &args[0], &args[1], Ordering::Greater)
+}
+
+// clamp low high number
+fn clamp(args: &[Constant], _: &Environment) -> NativeResult {
+    let low = pick("clamp", &args[2], &args[0], Ordering::Greater)?;
+    pick("clamp", &low, &args[1], Ordering::Less)
+}
+
+/// Returns `lhs` if it compares as `keep` against `rhs` otherwise `rhs`,
+/// promoting the result to a Float when the two are mixed.
+fn pick(name: &str, lhs: &Constant, rhs: &Constant, keep: Ordering) -> NativeResult {
+    let picked = if compare(lhs, rhs)? == keep { lhs } else { rhs };
+    match (lhs, rhs) {
+        (Constant::Int(_), Constant::Int(_)) => Ok(picked.clone()),
+        _ => Ok(Constant::Float(float(name, picked)?)),
+    }
+}
+
+// gcd int int
+fn gcd(args: &[Constant], _: &Environment) -> NativeResult {
+    euclid(int("gcd", &args[0])?, int("gcd", &args[1])?)
+        .map(Constant::Int)
+        .ok_or_else(|| overflow("gcd"))
+}
+
+// lcm int int
+fn lcm(args: &[Constant], _: &Environment) -> NativeResult {
+    let (a, b) = (int("lcm", &args[0])?, int("lcm", &args[1])?);
+    if a == 0 || b == 0 {
+        return Ok(Constant::Int(0));
+    }
+    euclid(a, b)
+        .and_then(|gcd| (a / gcd).checked_mul(b))
+        .and_then(i128::checked_abs)
+        .map(Constant::Int)
+        .ok_or_else(|| overflow("lcm"))
+}
+
+// None when the result is the magnitude of i128::MIN.
+fn euclid(mut a: i128, mut b: i128) -> Option<i128> {
+    while b != 0 {
+        (a, b) = (b, a.wrapping_rem(b));
+    }
+    a.checked_abs()
+}
+
+#[test]
+fn math_natives() {
+    let env = Environment::new();
+    assert_eq!(
+        pow(&[Constant::Int(2), Constant::Int(10)], &env).unwrap(),
+        Constant::Int(1024)
+    );
+    assert_eq!(
+        pow(&[Constant::Int(2), Constant::Int(-1)], &env).unwrap(),
+        Constant::Float(0.5)
+    );
+    assert_eq!(
+        max(&[Constant::Int(2), Constant::Float(1.5)], &env).unwrap(),
+        Constant::Float(2.0)
+    );
+    assert_eq!(
+        clamp(
+            &[Constant::Int(0), Constant::Int(10), Constant::Int(12)],
+            &env
+        )
+        .unwrap(),
+        Constant::Int(10)
+    );
+    assert_eq!(
+        gcd(&[Constant::Int(12), Constant::Int(-18)], &env).unwrap(),
+        Constant::Int(6)
+    );
+    assert_eq!(
+        lcm(&[Constant::Int(4), Constant::Int(6)], &env).unwrap(),
+        Constant::Int(12)
+    );
+    assert!(abs(&[Constant::Int(i128::MIN)], &env).is_err());
+    assert!(lcm(&[Constant::Int(i128::MAX), Constant::Int(2)], &env).is_err());
+    assert!(gcd(&[Constant::Int(i128::MIN), Constant::Int(0)], &env).is_err());
+    assert_eq!(
+        round_op("round", &Constant::Float(2.5), f64::round).unwrap(),
+        Constant::Int(3)
+    );
+    assert!(round_op("floor", &Constant::Float(f64::NAN), f64::floor).is_err());
+    assert!(round_op("ceil", &Constant::Float(f64::INFINITY), f64::ceil).is_err());
+    assert!(round_op("round", &Constant::Float(1e300), f64::round).is_err());
+}
@@ -0,0 +1,196 @@
+/*
+ * builtins/mod.rs
+ *
+ * Registry of Native Functions implemented in Rust.  `call::eval` falls
+ * back to the registry when an IDENTIFIER is not in the Environment.
+ */
+mod csv;
+mod fs;
+mod io;
+mod json;
+mod list;
+mod math;
+mod permissions;
+mod process;
+mod random;
+mod record;
+mod regex;
+mod result;
+mod string;
+mod time;
+
+use super::tree_walker::{
+    apply,
+    error::{Error, ErrorKind, Frame},
+};
+use super::Environment;
+use crate::parser::{Constant, Span};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+pub use permissions::{set_permissions, Permissions};
+pub use random::set_seed;
+
+pub type NativeResult = Result<Constant, Error>;
+pub type NativeFn = fn(&[Constant], &Environment) -> NativeResult;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Native {
+    pub name: &'static str,
+    pub arity: usize,
+    pub func: NativeFn,
+}
+
+#[derive(Debug, Default)]
+pub struct Registry {
+    natives: HashMap<&'static str, Native>,
+}
+
+impl Registry {
+    pub fn register(&mut self, name: &'static str, arity: usize, func: NativeFn) {
+        self.natives.insert(name, Native { name, arity, func });
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Native> {
+        self.natives.get(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.natives.keys().copied()
+    }
+}
+
+pub fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut registry = Registry::default();
+        csv::register(&mut registry);
+        fs::register(&mut registry);
+        io::register(&mut registry);
+        json::register(&mut registry);
+        list::register(&mut registry);
+        math::register(&mut registry);
+        process::register(&mut registry);
+        random::register(&mut registry);
+        record::register(&mut registry);
+        regex::register(&mut registry);
+        result::register(&mut registry);
+        string::register(&mut registry);
+        time::register(&mut registry);
+        registry
+    })
+}
+
+pub fn lookup(name: &str) -> Option<&'static Native> {
+    registry().get(name)
+}
+
+/// Calls a Function value from inside a Native Function, an Error from it
+/// gets a Frame for the call without a Span as there is no call in the source.
+fn call(func: &Constant, args: Vec<Constant>, env: &Environment) -> NativeResult {
+    let frame = match func {
+        Constant::Function { name, .. } | Constant::Native { name, .. } => {
+            Some(Frame::new(name, Span::default(), args.len()))
+        }
+        _ => None,
+    };
+    apply(func.clone(), args, env.clone())
+        .map(|(result, _)| result)
+        .map_err(|e| match frame {
+            Some(frame) => e.called(frame),
+            None => e,
+        })
+}
+
+/// Turns a Rust Result into an `Ok` or `Err` Constant.
+fn to_result<E: Display>(result: Result<Constant, E>) -> Constant {
+    match result {
+        Ok(c) => Constant::Ok(Box::new(c)),
+        Err(e) => Constant::Err(Box::new(Constant::String(e.to_string()))),
+    }
+}
+
+fn type_error(name: &str, expected: &str, found: &Constant) -> Error {
+    Error::new(
+        &format!(
+            "{} expected a <{}> Type but found a <{}> Type",
+            name,
+            expected,
+            found.name()
+        ),
+        ErrorKind::TypeError,
+    )
+}
+
+fn overflow(name: &str) -> Error {
+    Error::new(
+        &format!("{} overflowed the range of an Int", name),
+        ErrorKind::ValueError,
+    )
+}
+
+fn list<'a>(name: &str, constant: &'a Constant) -> Result<&'a [Constant], Error> {
+    match constant {
+        Constant::List(list) => Ok(list),
+        c => Err(type_error(name, "List", c)),
+    }
+}
+
+fn string<'a>(name: &str, constant: &'a Constant) -> Result<&'a str, Error> {
+    match constant {
+        Constant::String(s) => Ok(s),
+        c => Err(type_error(name, "String", c)),
+    }
+}
+
+fn int(name: &str, constant: &Constant) -> Result<i128, Error> {
+    match constant {
+        Constant::Int(i) => Ok(*i),
+        c => Err(type_error(name, "Int", c)),
+    }
+}
+
+fn record<'a>(name: &str, constant: &'a Constant) -> Result<&'a BTreeMap<String, Constant>, Error> {
+    match constant {
+        Constant::Record(record) => Ok(record),
+        c => Err(type_error(name, "Record", c)),
+    }
+}
+
+fn boolean(name: &str, constant: &Constant) -> Result<bool, Error> {
+    match constant {
+        Constant::Boolean(b) => Ok(*b),
+        c => Err(type_error(name, "Boolean", c)),
+    }
+}
+
+/// Orders two Constants, promoting Int to Float when they are mixed.
+fn compare(lhs: &Constant, rhs: &Constant) -> Result<Ordering, Error> {
+    match (lhs, rhs) {
+        (Constant::Int(i1), Constant::Int(i2)) => Ok(i1.cmp(i2)),
+        (Constant::Float(f1), Constant::Float(f2)) => Ok(f1.total_cmp(f2)),
+        (Constant::Int(i1), Constant::Float(f2)) => Ok((*i1 as f64).total_cmp(f2)),
+        (Constant::Float(f1), Constant::Int(i2)) => Ok(f1.total_cmp(&(*i2 as f64))),
+        (Constant::String(s1), Constant::String(s2)) => Ok(s1.cmp(s2)),
+        (Constant::Boolean(b1), Constant::Boolean(b2)) => Ok(b1.cmp(b2)),
+        (Constant::List(l1), Constant::List(l2)) => {
+            for (c1, c2) in l1.iter().zip(l2) {
+                match compare(c1, c2)? {
+                    Ordering::Equal => continue,
+                    ordering => return Ok(ordering),
+                }
+            }
+            Ok(l1.len().cmp(&l2.len()))
+        }
+        (c1, c2) => Err(Error::new(
+            &format!(
+                "Can not compare a <{}> Type with a <{}> Type",
+                c1.name(),
+                c2.name()
+            ),
+            ErrorKind::TypeError,
+        )),
+    }
+}
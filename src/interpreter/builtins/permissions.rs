@@ -0,0 +1,150 @@
+/*
+ * builtins/permissions.rs
+ *
+ * Opt in capabilities for Native Functions with side effects, set from
+ * `--allow-read`, `--allow-write`, `--allow-exec`, `--allow-env` and
+ * `--allow-all`.  Everything is denied until it is allowed.
+ */
+use super::{Error, ErrorKind};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Access {
+    #[default]
+    Denied,
+    All,
+    Paths(Vec<PathBuf>),
+}
+
+impl Access {
+    fn allows(&self, path: &Path) -> bool {
+        match self {
+            Self::Denied => false,
+            Self::All => true,
+            Self::Paths(paths) => {
+                let path = resolve(path);
+                paths.iter().any(|allowed| path.starts_with(allowed))
+            }
+        }
+    }
+
+    // `--allow-read` allows everything, `--allow-read=a,b` only a and b.
+    fn parse(value: Option<&str>) -> Self {
+        match value {
+            None => Self::All,
+            Some(paths) => Self::Paths(
+                paths
+                    .split(',')
+                    .filter(|path| !path.is_empty())
+                    .map(|path| resolve(Path::new(path)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Permissions {
+    pub read: Access,
+    pub write: Access,
+    pub exec: bool,
+    pub env: bool,
+}
+
+impl Permissions {
+    pub fn all() -> Self {
+        Self {
+            read: Access::All,
+            write: Access::All,
+            exec: true,
+            env: true,
+        }
+    }
+
+    /// Applies a command line flag, returning false if it is not a permission flag.
+    pub fn parse_flag(&mut self, arg: &str) -> bool {
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value)),
+            None => (arg, None),
+        };
+        match (flag, value) {
+            ("--allow-read", value) => self.read = Access::parse(value),
+            ("--allow-write", value) => self.write = Access::parse(value),
+            ("--allow-exec", None) => self.exec = true,
+            ("--allow-env", None) => self.env = true,
+            ("--allow-all", None) => *self = Self::all(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+thread_local! {
+    static PERMISSIONS: RefCell<Permissions> = RefCell::new(Permissions::default());
+}
+
+pub fn set_permissions(permissions: Permissions) {
+    PERMISSIONS.with(|p| *p.borrow_mut() = permissions);
+}
+
+pub fn check_read(name: &str, path: &str) -> Result<(), Error> {
+    match PERMISSIONS.with(|p| p.borrow().read.allows(Path::new(path))) {
+        true => Ok(()),
+        false => Err(denied(name, &format!("read {:?}", path), "--allow-read")),
+    }
+}
+
+pub fn check_write(name: &str, path: &str) -> Result<(), Error> {
+    match PERMISSIONS.with(|p| p.borrow().write.allows(Path::new(path))) {
+        true => Ok(()),
+        false => Err(denied(name, &format!("write {:?}", path), "--allow-write")),
+    }
+}
+
+pub fn check_exec(name: &str, program: &str) -> Result<(), Error> {
+    match PERMISSIONS.with(|p| p.borrow().exec) {
+        true => Ok(()),
+        false => Err(denied(name, &format!("run {:?}", program), "--allow-exec")),
+    }
+}
+
+pub fn check_env(name: &str, var: &str) -> Result<(), Error> {
+    match PERMISSIONS.with(|p| p.borrow().env) {
+        true => Ok(()),
+        false => Err(denied(name, &format!("read {:?}", var), "--allow-env")),
+    }
+}
+
+fn denied(name: &str, action: &str, flag: &str) -> Error {
+    Error::new(
+        &format!("{} is not allowed to {}, run with {}", name, action, flag),
+        ErrorKind::PermissionDenied,
+    )
+}
+
+/// Absolute path with symlinks resolved as far as the path exists.
+fn resolve(path: &Path) -> PathBuf {
+    if let Ok(path) = path.canonicalize() {
+        return path;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(file_name)) => resolve(parent).join(file_name),
+        _ => std::env::current_dir().unwrap_or_default().join(path),
+    }
+}
+
+#[test]
+fn permission_flags() {
+    let mut permissions = Permissions::default();
+    assert!(permissions.parse_flag("--allow-read=./src"));
+    assert!(permissions.parse_flag("--allow-exec"));
+    assert!(!permissions.parse_flag("--allow-exec=ls"));
+    assert!(!permissions.parse_flag("script.uln"));
+    assert!(permissions.read.allows(Path::new("src/main.rs")));
+    assert!(permissions.read.allows(Path::new("./src/not_made_yet.uln")));
+    assert!(!permissions.read.allows(Path::new("Cargo.toml")));
+    assert_eq!(permissions.write, Access::Denied);
+    assert!(permissions.exec);
+    assert!(!permissions.env);
+}
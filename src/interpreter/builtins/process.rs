@@ -0,0 +1,132 @@
+/*
+ * builtins/process.rs
+ *
+ * Functions for the running process and its environment.
+ */
+use super::permissions::{check_env, check_exec};
+use super::{int, list, string, to_result, Environment, Error, ErrorKind, NativeResult, Registry};
+use crate::parser::Constant;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::process::Command;
+
+pub fn register(registry: &mut Registry) {
+    registry.register("env_var", 1, env_var);
+    registry.register("exit", 1, exit);
+    registry.register("exec", 2, exec);
+    registry.register("exec_stream", 2, exec_stream);
+}
+
+// env_var name  → Ok(value) or Err when it is not set.
+fn env_var(args: &[Constant], _: &Environment) -> NativeResult {
+    let name = string("env_var", &args[0])?;
+    check_env("env_var", name)?;
+    Ok(to_result(std::env::var(name).map(Constant::String)))
+}
+
+// exit code  → code has to be from 0 to 255.
+fn exit(args: &[Constant], _: &Environment) -> NativeResult {
+    let code = exit_code(int("exit", &args[0])?)?;
+    let _ = std::io::stdout().flush();
+    std::process::exit(code)
+}
+
+fn exit_code(code: i128) -> Result<i32, Error> {
+    u8::try_from(code).map(i32::from).map_err(|_| {
+        Error::new(
+            &format!("exit code {} is not from 0 to 255", code),
+            ErrorKind::ValueError,
+        )
+    })
+}
+
+// exec command [arg, ...]  → Ok({status, stdout, stderr}) once it finishes.
+fn exec(args: &[Constant], _: &Environment) -> NativeResult {
+    let mut command = command("exec", args)?;
+    Ok(to_result(command.output().map(|output| {
+        let mut record = BTreeMap::new();
+        record.insert("status".into(), status(output.status));
+        record.insert(
+            "stdout".into(),
+            Constant::String(String::from_utf8_lossy(&output.stdout).into_owned()),
+        );
+        record.insert(
+            "stderr".into(),
+            Constant::String(String::from_utf8_lossy(&output.stderr).into_owned()),
+        );
+        Constant::Record(record)
+    })))
+}
+
+// exec_stream command [arg, ...]  → Ok(status), output goes straight to ours.
+fn exec_stream(args: &[Constant], _: &Environment) -> NativeResult {
+    let mut command = command("exec_stream", args)?;
+    let _ = std::io::stdout().flush();
+    Ok(to_result(command.status().map(status)))
+}
+
+fn command(name: &str, args: &[Constant]) -> Result<Command, Error> {
+    let program = string(name, &args[0])?;
+    check_exec(name, program)?;
+    let mut command = Command::new(program);
+    for arg in list(name, &args[1])? {
+        command.arg(string(name, arg)?);
+    }
+    Ok(command)
+}
+
+// A process killed by a signal has no exit code.
+fn status(status: std::process::ExitStatus) -> Constant {
+    Constant::Int(status.code().unwrap_or(-1) as i128)
+}
+
+#[cfg(test)]
+fn sh(script: &str) -> [Constant; 2] {
+    let text = |s: &str| Constant::String(s.into());
+    [text("sh"), Constant::List(vec![text("-c"), text(script)])]
+}
+
+#[cfg(test)]
+fn exec_record(args: &[Constant]) -> BTreeMap<String, Constant> {
+    match exec(args, &Environment::new()).unwrap() {
+        Constant::Ok(record) => match *record {
+            Constant::Record(record) => record,
+            c => panic!("expected a Record but found {:?}", c),
+        },
+        c => panic!("expected Ok but found {:?}", c),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn exec_success() {
+    super::set_permissions(super::Permissions::all());
+    let record = exec_record(&sh("echo out; echo err >&2"));
+    assert_eq!(record["status"], Constant::Int(0));
+    assert_eq!(record["stdout"], Constant::String("out\n".into()));
+    assert_eq!(record["stderr"], Constant::String("err\n".into()));
+}
+
+#[cfg(unix)]
+#[test]
+fn exec_non_zero_exit() {
+    super::set_permissions(super::Permissions::all());
+    let record = exec_record(&sh("exit 3"));
+    assert_eq!(record["status"], Constant::Int(3));
+    assert_eq!(record["stdout"], Constant::String(String::new()));
+}
+
+#[test]
+fn exec_without_permission() {
+    super::set_permissions(super::Permissions::default());
+    let error = exec(&sh("echo out"), &Environment::new()).unwrap_err();
+    assert!(matches!(error.kind, super::ErrorKind::PermissionDenied));
+    assert!(error.msg.contains("--allow-exec"));
+}
+
+#[test]
+fn exit_codes_are_checked() {
+    assert_eq!(exit_code(3).unwrap(), 3);
+    assert!(exit(&[Constant::Int(256)], &Environment::new()).is_err());
+    assert!(exit(&[Constant::Int(-1)], &Environment::new()).is_err());
+}
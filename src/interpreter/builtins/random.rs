@@ -0,0 +1,101 @@
+/*
+ * builtins/random.rs
+ *
+ * Pseudo Random Functions.  The generator is SplitMix64 so a `--seed`
+ * gives the same numbers on every run and platform.
+ */
+use super::{int, list, Environment, Error, ErrorKind, NativeResult, Registry};
+use crate::parser::Constant;
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+thread_local! {
+    static STATE: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+pub fn set_seed(seed: u64) {
+    STATE.with(|state| state.set(Some(seed)));
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register("random_int", 2, random_int);
+    registry.register("random_float", 0, random_float);
+    registry.register("shuffle", 1, shuffle);
+}
+
+fn next_u64() -> u64 {
+    STATE.with(|state| {
+        let seed = state.get().unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64
+        });
+        let next = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        state.set(Some(next));
+        let mut z = next;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    })
+}
+
+// A number from 0 up to but not including `bound`.
+fn below(bound: u128) -> u128 {
+    let random = ((next_u64() as u128) << 64) | next_u64() as u128;
+    random % bound
+}
+
+// random_int low high  → an Int from low to high inclusive.
+fn random_int(args: &[Constant], _: &Environment) -> NativeResult {
+    let low = int("random_int", &args[0])?;
+    let high = int("random_int", &args[1])?;
+    if low > high {
+        return Err(Error::new(
+            &format!("random_int low {} is greater than high {}", low, high),
+            ErrorKind::ValueError,
+        ));
+    }
+    let span = high.abs_diff(low).saturating_add(1);
+    Ok(Constant::Int(low.wrapping_add(below(span) as i128)))
+}
+
+// random_float  → a Float from 0 up to but not including 1.
+fn random_float(_: &[Constant], _: &Environment) -> NativeResult {
+    Ok(Constant::Float(
+        (next_u64() >> 11) as f64 / (1u64 << 53) as f64,
+    ))
+}
+
+// shuffle list
+fn shuffle(args: &[Constant], _: &Environment) -> NativeResult {
+    let mut result = list("shuffle", &args[0])?.to_vec();
+    for i in (1..result.len()).rev() {
+        result.swap(i, below(i as u128 + 1) as usize);
+    }
+    Ok(Constant::List(result))
+}
+
+#[test]
+fn seeded_random_repeats() {
+    let env = Environment::new();
+    let run = || {
+        set_seed(42);
+        let list = Constant::List((0..10).map(Constant::Int).collect());
+        (
+            random_int(&[Constant::Int(-5), Constant::Int(5)], &env).unwrap(),
+            random_float(&[], &env).unwrap(),
+            shuffle(&[list], &env).unwrap(),
+        )
+    };
+    let first = run();
+    assert_eq!(first, run());
+    match first {
+        (Constant::Int(i), Constant::Float(f), Constant::List(list)) => {
+            assert!((-5..=5).contains(&i));
+            assert!((0.0..1.0).contains(&f));
+            assert_eq!(list.len(), 10);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
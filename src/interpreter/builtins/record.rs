@@ -0,0 +1,52 @@
+/*
+ * builtins/record.rs
+ *
+ * Record Functions.
+ */
+use super::{record, string, Environment, Error, ErrorKind, NativeResult, Registry};
+use crate::parser::Constant;
+
+pub fn register(registry: &mut Registry) {
+    registry.register("get", 2, get);
+    registry.register("has_key", 2, has_key);
+    registry.register("keys", 1, keys);
+    registry.register("values", 1, values);
+}
+
+// get key record
+fn get(args: &[Constant], _: &Environment) -> NativeResult {
+    let key = string("get", &args[0])?;
+    match record("get", &args[1])?.get(key) {
+        Some(value) => Ok(value.clone()),
+        None => Err(Error::new(
+            &format!("Record has no key {:?}", key),
+            ErrorKind::ValueError,
+        )),
+    }
+}
+
+// has_key key record
+fn has_key(args: &[Constant], _: &Environment) -> NativeResult {
+    let key = string("has_key", &args[0])?;
+    Ok(Constant::Boolean(
+        record("has_key", &args[1])?.contains_key(key),
+    ))
+}
+
+// keys record  → sorted keys.
+fn keys(args: &[Constant], _: &Environment) -> NativeResult {
+    Ok(Constant::List(
+        record("keys", &args[0])?
+            .keys()
+            .cloned()
+            .map(Constant::String)
+            .collect(),
+    ))
+}
+
+// values record  → values in the order of their keys.
+fn values(args: &[Constant], _: &Environment) -> NativeResult {
+    Ok(Constant::List(
+        record("values", &args[0])?.values().cloned().collect(),
+    ))
+}
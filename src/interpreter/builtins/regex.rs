@@ -0,0 +1,158 @@
+/*
+ * builtins/regex.rs
+ *
+ * Regular Expression Functions.  Patterns are either a Regex value from
+ * `regex` or a String, and Strings are compiled once and cached.
+ */
+use super::{string, to_result, type_error, Environment, Error, ErrorKind, NativeResult, Registry};
+use crate::parser::{Constant, Regex};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+pub fn register(registry: &mut Registry) {
+    registry.register("regex", 1, regex);
+    registry.register("regex_match", 2, regex_match);
+    registry.register("regex_find_all", 2, regex_find_all);
+    registry.register("regex_captures", 2, regex_captures);
+    registry.register("regex_replace", 3, regex_replace);
+    registry.register("regex_split", 2, regex_split);
+}
+
+// Patterns built at runtime could grow the cache forever, so it starts over
+// once it holds this many.
+const CACHE_SIZE: usize = 256;
+
+thread_local! {
+    static CACHE: RefCell<HashMap<String, regex::Regex>> = RefCell::new(HashMap::new());
+}
+
+fn compile(pattern: &str) -> Result<regex::Regex, regex::Error> {
+    CACHE.with(|cache| {
+        if let Some(regex) = cache.borrow().get(pattern) {
+            return Ok(regex.clone());
+        }
+        let regex = regex::Regex::new(pattern)?;
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= CACHE_SIZE {
+            cache.clear();
+        }
+        cache.insert(pattern.into(), regex.clone());
+        Ok(regex)
+    })
+}
+
+fn pattern(name: &str, constant: &Constant) -> Result<regex::Regex, Error> {
+    match constant {
+        Constant::Regex(Regex(regex)) => Ok(regex.clone()),
+        Constant::String(pattern) => compile(pattern).map_err(|e| {
+            Error::new(
+                &format!("{} got an invalid pattern: {}", name, e),
+                ErrorKind::ValueError,
+            )
+        }),
+        c => Err(type_error(name, "Regex or String", c)),
+    }
+}
+
+// regex pattern  → Ok(Regex) or Err(message)
+fn regex(args: &[Constant], _: &Environment) -> NativeResult {
+    let pattern = string("regex", &args[0])?;
+    Ok(to_result(
+        compile(pattern).map(|regex| Constant::Regex(Regex(regex))),
+    ))
+}
+
+// regex_match pattern string
+fn regex_match(args: &[Constant], _: &Environment) -> NativeResult {
+    let regex = pattern("regex_match", &args[0])?;
+    Ok(Constant::Boolean(
+        regex.is_match(string("regex_match", &args[1])?),
+    ))
+}
+
+// regex_find_all pattern string  → [match, ...]
+fn regex_find_all(args: &[Constant], _: &Environment) -> NativeResult {
+    let regex = pattern("regex_find_all", &args[0])?;
+    Ok(Constant::List(
+        regex
+            .find_iter(string("regex_find_all", &args[1])?)
+            .map(|m| Constant::String(m.as_str().into()))
+            .collect(),
+    ))
+}
+
+// regex_captures pattern string  → [whole match, group 1, ...] of the first
+// match, a group that did not take part is Null and no match is [].
+fn regex_captures(args: &[Constant], _: &Environment) -> NativeResult {
+    let regex = pattern("regex_captures", &args[0])?;
+    match regex.captures(string("regex_captures", &args[1])?) {
+        Some(captures) => Ok(Constant::List(
+            captures
+                .iter()
+                .map(|group| match group {
+                    Some(m) => Constant::String(m.as_str().into()),
+                    None => Constant::Null,
+                })
+                .collect(),
+        )),
+        None => Ok(Constant::List(Vec::new())),
+    }
+}
+
+// regex_replace pattern replacement string  → every match replaced, `$1`
+// in the replacement is the first group.
+fn regex_replace(args: &[Constant], _: &Environment) -> NativeResult {
+    let regex = pattern("regex_replace", &args[0])?;
+    let replacement = string("regex_replace", &args[1])?;
+    Ok(Constant::String(
+        regex
+            .replace_all(string("regex_replace", &args[2])?, replacement)
+            .into_owned(),
+    ))
+}
+
+// regex_split pattern string
+fn regex_split(args: &[Constant], _: &Environment) -> NativeResult {
+    let regex = pattern("regex_split", &args[0])?;
+    Ok(Constant::List(
+        regex
+            .split(string("regex_split", &args[1])?)
+            .map(|s| Constant::String(s.into()))
+            .collect(),
+    ))
+}
+
+#[test]
+fn regex_natives() {
+    let env = Environment::new();
+    let s = |s: &str| Constant::String(s.into());
+    let digits = match regex(&[s("([a-z]+)(\\d+)?")], &env).unwrap() {
+        Constant::Ok(regex) => *regex,
+        c => panic!("expected Ok found {}", c),
+    };
+    assert_eq!(
+        regex_find_all(&[digits.clone(), s("ab12 cd")], &env).unwrap(),
+        Constant::List(vec![s("ab12"), s("cd")])
+    );
+    assert_eq!(
+        regex_captures(&[digits.clone(), s("-- cd")], &env).unwrap(),
+        Constant::List(vec![s("cd"), s("cd"), Constant::Null])
+    );
+    assert_eq!(
+        regex_replace(&[digits, s("$2$1"), s("ab12")], &env).unwrap(),
+        s("12ab")
+    );
+    assert_eq!(
+        regex_split(&[s(",\\s*"), s("a, b,c")], &env).unwrap(),
+        Constant::List(vec![s("a"), s("b"), s("c")])
+    );
+    assert!(regex_match(&[s("("), s("x")], &env).is_err());
+}
+
+#[test]
+fn regex_cache_is_capped() {
+    for i in 0..CACHE_SIZE * 2 {
+        compile(&format!("a{{{}}}", i)).unwrap();
+    }
+    assert!(CACHE.with(|cache| cache.borrow().len()) <= CACHE_SIZE);
+}
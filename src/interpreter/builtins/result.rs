@@ -0,0 +1,60 @@
+/*
+ * builtins/result.rs
+ *
+ * Functions for the `Ok` and `Err` Result values returned by Native
+ * Functions that can fail.
+ */
+use super::{type_error, Environment, Error, ErrorKind, NativeResult, Registry};
+use crate::parser::Constant;
+
+pub fn register(registry: &mut Registry) {
+    registry.register("ok", 1, |args, _| {
+        Ok(Constant::Ok(Box::new(args[0].clone())))
+    });
+    registry.register("err", 1, |args, _| {
+        Ok(Constant::Err(Box::new(args[0].clone())))
+    });
+    registry.register("is_ok", 1, is_ok);
+    registry.register("is_err", 1, is_err);
+    registry.register("unwrap", 1, unwrap);
+    registry.register("unwrap_or", 2, unwrap_or);
+}
+
+// is_ok result
+fn is_ok(args: &[Constant], _: &Environment) -> NativeResult {
+    match &args[0] {
+        Constant::Ok(_) => Ok(Constant::Boolean(true)),
+        Constant::Err(_) => Ok(Constant::Boolean(false)),
+        c => Err(type_error("is_ok", "Result", c)),
+    }
+}
+
+// is_err result
+fn is_err(args: &[Constant], _: &Environment) -> NativeResult {
+    match &args[0] {
+        Constant::Ok(_) => Ok(Constant::Boolean(false)),
+        Constant::Err(_) => Ok(Constant::Boolean(true)),
+        c => Err(type_error("is_err", "Result", c)),
+    }
+}
+
+// unwrap result  → the `Ok` value, an `Err` stops the program.
+fn unwrap(args: &[Constant], _: &Environment) -> NativeResult {
+    match &args[0] {
+        Constant::Ok(c) => Ok(*c.clone()),
+        Constant::Err(e) => Err(Error::new(
+            &format!("Called unwrap on Err({})", e),
+            ErrorKind::ValueError,
+        )),
+        c => Err(type_error("unwrap", "Result", c)),
+    }
+}
+
+// unwrap_or default result
+fn unwrap_or(args: &[Constant], _: &Environment) -> NativeResult {
+    match &args[1] {
+        Constant::Ok(c) => Ok(*c.clone()),
+        Constant::Err(_) => Ok(args[0].clone()),
+        c => Err(type_error("unwrap_or", "Result", c)),
+    }
+}
@@ -0,0 +1,190 @@
+/*
+ * builtins/string.rs
+ *
+ * String Functions.  The String being worked on is always the last
+ * argument so they can be partially applied, `map (split ",") lines`.
+ */
+use super::{int, list, string, Environment, Error, ErrorKind, NativeResult, Registry};
+use crate::parser::Constant;
+
+pub fn register(registry: &mut Registry) {
+    registry.register("chars", 1, chars);
+    registry.register("split", 2, split);
+    registry.register("join", 2, join);
+    registry.register("trim", 1, trim);
+    registry.register("upper", 1, upper);
+    registry.register("lower", 1, lower);
+    registry.register("starts_with", 2, starts_with);
+    registry.register("replace", 3, replace);
+    registry.register("substring", 3, substring);
+    registry.register("repeat", 2, repeat);
+    registry.register("parse_int", 1, parse_int);
+    registry.register("parse_float", 1, parse_float);
+}
+
+// chars string  → list of single char Strings.
+fn chars(args: &[Constant], _: &Environment) -> NativeResult {
+    Ok(Constant::List(
+        string("chars", &args[0])?
+            .chars()
+            .map(|c| Constant::String(c.into()))
+            .collect(),
+    ))
+}
+
+// split separator string
+fn split(args: &[Constant], _: &Environment) -> NativeResult {
+    let separator = string("split", &args[0])?;
+    Ok(Constant::List(
+        string("split", &args[1])?
+            .split(separator)
+            .map(|s| Constant::String(s.into()))
+            .collect(),
+    ))
+}
+
+// join separator list
+fn join(args: &[Constant], _: &Environment) -> NativeResult {
+    let separator = string("join", &args[0])?;
+    let mut parts = Vec::new();
+    for item in list("join", &args[1])? {
+        parts.push(string("join", item)?);
+    }
+    Ok(Constant::String(parts.join(separator)))
+}
+
+// trim string
+fn trim(args: &[Constant], _: &Environment) -> NativeResult {
+    Ok(Constant::String(string("trim", &args[0])?.trim().into()))
+}
+
+// upper string
+fn upper(args: &[Constant], _: &Environment) -> NativeResult {
+    Ok(Constant::String(string("upper", &args[0])?.to_uppercase()))
+}
+
+// lower string
+fn lower(args: &[Constant], _: &Environment) -> NativeResult {
+    Ok(Constant::String(string("lower", &args[0])?.to_lowercase()))
+}
+
+// starts_with prefix string
+fn starts_with(args: &[Constant], _: &Environment) -> NativeResult {
+    let prefix = string("starts_with", &args[0])?;
+    Ok(Constant::Boolean(
+        string("starts_with", &args[1])?.starts_with(prefix),
+    ))
+}
+
+// replace from to string
+fn replace(args: &[Constant], _: &Environment) -> NativeResult {
+    let from = string("replace", &args[0])?;
+    let to = string("replace", &args[1])?;
+    Ok(Constant::String(
+        string("replace", &args[2])?.replace(from, to),
+    ))
+}
+
+// substring start end string  → chars from start up to but not including end.
+fn substring(args: &[Constant], _: &Environment) -> NativeResult {
+    let start = count("substring", int("substring", &args[0])?)?;
+    let end = count("substring", int("substring", &args[1])?)?;
+    Ok(Constant::String(
+        string("substring", &args[2])?
+            .chars()
+            .skip(start)
+            .take(end.saturating_sub(start))
+            .collect(),
+    ))
+}
+
+/// The longest String `repeat` will make, in bytes.
+const MAX_REPEAT: usize = 1 << 30;
+
+// repeat count string  → the String `count` times, up to MAX_REPEAT bytes.
+fn repeat(args: &[Constant], _: &Environment) -> NativeResult {
+    let times = count("repeat", int("repeat", &args[0])?)?;
+    let s = string("repeat", &args[1])?;
+    match s.len().checked_mul(times) {
+        Some(len) if len <= MAX_REPEAT => Ok(Constant::String(s.repeat(times))),
+        _ => Err(Error::new(
+            &format!(
+                "repeat {} times would be longer than {} bytes",
+                times, MAX_REPEAT
+            ),
+            ErrorKind::ValueError,
+        )),
+    }
+}
+
+/// An Int used as a count or index, it can not be negative.
+fn count(name: &str, n: i128) -> Result<usize, Error> {
+    usize::try_from(n).map_err(|_| {
+        Error::new(
+            &format!("{} needs a count from 0, found {}", name, n),
+            ErrorKind::ValueError,
+        )
+    })
+}
+
+// parse_int string
+fn parse_int(args: &[Constant], _: &Environment) -> NativeResult {
+    let s = string("parse_int", &args[0])?;
+    match s.trim().parse::<i128>() {
+        Ok(i) => Ok(Constant::Int(i)),
+        Err(_) => Err(parse_error(s, "Int")),
+    }
+}
+
+// parse_float string
+fn parse_float(args: &[Constant], _: &Environment) -> NativeResult {
+    let s = string("parse_float", &args[0])?;
+    match s.trim().parse::<f64>() {
+        Ok(f) => Ok(Constant::Float(f)),
+        Err(_) => Err(parse_error(s, "Float")),
+    }
+}
+
+fn parse_error(s: &str, type_name: &str) -> Error {
+    Error::new(
+        &format!("Can not parse {:?} as a <{}> Type", s, type_name),
+        ErrorKind::ValueError,
+    )
+}
+
+#[cfg(test)]
+fn strings(list: &[&str]) -> Constant {
+    Constant::List(
+        list.iter()
+            .map(|s| Constant::String(s.to_string()))
+            .collect(),
+    )
+}
+
+#[test]
+fn string_natives() {
+    let env = Environment::new();
+    let s = |s: &str| Constant::String(s.into());
+    assert_eq!(
+        split(&[s(","), s("a,b,c")], &env).unwrap(),
+        strings(&["a", "b", "c"])
+    );
+    assert_eq!(
+        join(&[s("-"), strings(&["a", "b"])], &env).unwrap(),
+        s("a-b")
+    );
+    assert_eq!(
+        substring(&[Constant::Int(1), Constant::Int(3), s("héllo")], &env).unwrap(),
+        s("él")
+    );
+    assert!(substring(&[Constant::Int(-1), Constant::Int(3), s("abc")], &env).is_err());
+    assert_eq!(
+        repeat(&[Constant::Int(3), s("ab")], &env).unwrap(),
+        s("ababab")
+    );
+    assert!(repeat(&[Constant::Int(-1), s("ab")], &env).is_err());
+    assert!(repeat(&[Constant::Int(1 << 62), s("ab")], &env).is_err());
+    assert!(repeat(&[Constant::Int(i128::MAX), s("ab")], &env).is_err());
+    assert_eq!(parse_int(&[s(" 42 ")], &env).unwrap(), Constant::Int(42));
+    assert!(parse_float(&[s("nope")], &env).is_err());
+}
@@ -0,0 +1,113 @@
+/*
+ * builtins/time.rs
+ *
+ * Clock and Date Functions.  Times are milliseconds since the Unix epoch
+ * and dates are formatted in UTC.
+ */
+use super::{int, string, Environment, Error, ErrorKind, NativeResult, Registry};
+use crate::parser::Constant;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+static START: OnceLock<Instant> = OnceLock::new();
+
+pub fn register(registry: &mut Registry) {
+    START.get_or_init(Instant::now);
+    registry.register("now", 0, now);
+    registry.register("monotonic", 0, monotonic);
+    registry.register("sleep", 1, sleep);
+    registry.register("format_time", 2, format_time);
+}
+
+// now  → wall clock milliseconds since the Unix epoch.
+fn now(_: &[Constant], _: &Environment) -> NativeResult {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Ok(Constant::Int(since_epoch.as_millis() as i128))
+}
+
+// monotonic  → milliseconds since the program started, never goes backwards.
+fn monotonic(_: &[Constant], _: &Environment) -> NativeResult {
+    let start = START.get_or_init(Instant::now);
+    Ok(Constant::Float(start.elapsed().as_secs_f64() * 1000.0))
+}
+
+// sleep milliseconds
+fn sleep(args: &[Constant], _: &Environment) -> NativeResult {
+    let ms = int("sleep", &args[0])?;
+    std::thread::sleep(Duration::from_millis(ms.clamp(0, u64::MAX as i128) as u64));
+    Ok(Constant::Int(ms))
+}
+
+// format_time format milliseconds
+// %Y year, %m month, %d day, %H hour, %M minute, %S second, %f milliseconds, %% a %.
+fn format_time(args: &[Constant], _: &Environment) -> NativeResult {
+    let format = string("format_time", &args[0])?;
+    let millis = int("format_time", &args[1])?;
+    let seconds = millis.div_euclid(1000);
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let time = seconds.rem_euclid(86_400);
+    let mut out = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => out.push_str(&format!("{:04}", year)),
+            Some('m') => out.push_str(&format!("{:02}", month)),
+            Some('d') => out.push_str(&format!("{:02}", day)),
+            Some('H') => out.push_str(&format!("{:02}", time / 3600)),
+            Some('M') => out.push_str(&format!("{:02}", time % 3600 / 60)),
+            Some('S') => out.push_str(&format!("{:02}", time % 60)),
+            Some('f') => out.push_str(&format!("{:03}", millis.rem_euclid(1000))),
+            Some('%') => out.push('%'),
+            other => {
+                return Err(Error::new(
+                    &format!(
+                        "format_time does not know %{}",
+                        other.map(String::from).unwrap_or_default()
+                    ),
+                    ErrorKind::ValueError,
+                ))
+            }
+        }
+    }
+    Ok(Constant::String(out))
+}
+
+/// Days since 1970-01-01 to a (year, month, day) in the proleptic Gregorian calendar.
+fn civil_from_days(days: i128) -> (i128, i128, i128) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[test]
+fn format_time_utc() {
+    let env = Environment::new();
+    let format = |f: &str, ms: i128| {
+        format_time(&[Constant::String(f.into()), Constant::Int(ms)], &env).unwrap()
+    };
+    assert_eq!(
+        format("%Y-%m-%d %H:%M:%S.%f", 0),
+        Constant::String("1970-01-01 00:00:00.000".into())
+    );
+    assert_eq!(
+        format("%Y-%m-%d %H:%M:%S", 1_709_210_096_789),
+        Constant::String("2024-02-29 12:34:56".into())
+    );
+    assert_eq!(
+        format("%d/%m/%Y", -86_400_000),
+        Constant::String("31/12/1969".into())
+    );
+}
@@ -1,44 +1,58 @@
+mod builtins;
 mod tree_walker;
 // mod vm;
 
+use crate::error::Error;
 use crate::parser::{Constant, Expr};
+pub use builtins::{set_permissions, set_seed, Permissions};
+pub use tree_walker::error::{Error as RuntimeError, ErrorKind, Frame};
 
 /// Environment
+use std::cell::OnceCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 pub type Environment = HashMap<String, DataStruct>;
 
-pub fn interpreter(expressions: Vec<Expr>) -> Vec<Constant> {
-    let mut environment = Environment::new();
+/// Evaluates each expression in order, stopping at the first Error.
+pub fn interpreter(
+    expressions: Vec<Expr>,
+    mut environment: Environment,
+) -> Result<(Vec<Constant>, Environment), Error> {
     let mut constants = Vec::new();
     for expr in expressions {
-        match tree_walker::eval_expr(expr, environment.clone()) {
-            Ok((con, e)) => {
-                constants.push(con);
-                environment = e;
-            }
-            Err(e) => {
-                println!("{:#?}", e);
-            }
-        }
+        let (con, e) = tree_walker::eval_expr(expr, environment)?;
+        constants.push(con);
+        environment = e;
     }
-    constants
+    Ok((constants, environment))
 }
-pub fn interpreter_expr(expr: Expr, mut env: Environment) -> (Vec<Constant>, Environment) {
-    let mut constants = Vec::new();
-    match tree_walker::eval_expr(expr, env.clone()) {
-        Ok((con, e)) => {
-            constants.push(con);
-            env = e
-        }
-        Err(e) => {
-            println!("{:#?}", e);
-        }
-    }
-    (constants, env)
+
+/// Stack for the thread `with_stack` makes, enough for `MAX_DEPTH` calls
+/// in a debug build.
+const STACK_SIZE: usize = 1 << 30;
+
+/// Runs `f` on a thread with a stack big enough that deep recursion stops
+/// with a RecursionLimit Error rather than overflowing the stack.
+pub fn with_stack<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    std::thread::scope(|scope| {
+        let thread = std::thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, f)
+            .expect("could not start the interpreter thread");
+        thread
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
 }
 
 #[derive(Debug, Clone)]
 pub enum DataStruct {
     Function(String, Vec<String>, Expr),
-    Argument(Expr),
+    // A Function from the prelude that a program may redefine.
+    Prelude(String, Vec<String>, Expr),
+    // A parameter's value and the definition of the same name it hides.
+    Argument(Expr, Option<Box<DataStruct>>),
+    // A definition without parameters, its value is worked out the first
+    // time it is used and shared by every copy of the Environment.
+    Value(String, Expr, Rc<OnceCell<Constant>>),
 }
@@ -7,6 +7,7 @@ use crate::parser::{Constant, Expr, Operator};
 pub fn eval(op: Operator, lhs: Expr, rhs: Expr, env1: Environment) -> IResult {
     let (lhsr, env2) = eval_expr(lhs, env1)?;
     let (rhsr, env3) = eval_expr(rhs, env2)?;
+    let (lhsr, rhsr) = promote(lhsr, rhsr);
     match op {
         Operator::Minus => match (lhsr, rhsr) {
             (Constant::Int(i1), Constant::Int(i2)) => Ok((Constant::Int(i1 - i2), env3)),
@@ -75,6 +76,15 @@ pub fn eval(op: Operator, lhs: Expr, rhs: Expr, env1: Environment) -> IResult {
     }
 }
 
+/// Promotes an Int to a Float when it is paired with a Float.
+fn promote(lhs: Constant, rhs: Constant) -> (Constant, Constant) {
+    match (lhs, rhs) {
+        (Constant::Int(i), Constant::Float(f)) => (Constant::Float(i as f64), Constant::Float(f)),
+        (Constant::Float(f), Constant::Int(i)) => (Constant::Float(f), Constant::Float(i as f64)),
+        pair => pair,
+    }
+}
+
 fn type_error(op: Operator, lhs: Constant, rhs: Constant) -> IResult {
     Err(Error::new(
         &format!(
@@ -1,41 +1,185 @@
 use super::{
-    error::{Error, ErrorKind},
-    eval_expr, Environment, IResult,
+    error::{Error, ErrorKind, Frame},
+    eval_expr, suggest, Environment, IResult,
 };
-use crate::interpreter::DataStruct;
-use crate::parser::{Constant, Expr};
+use crate::interpreter::{builtins, DataStruct};
+use crate::parser::{Constant, Expr, Span};
+use std::cell::Cell;
 
-pub fn eval(ident: Expr, args: Vec<Box<Expr>>, env1: Environment) -> IResult {
-    let (ident, env2) = eval_expr(ident, env1)?;
+/// How many Function calls can be running at once, the interpreter runs on
+/// a thread with a stack big enough for this many, see `with_stack`.
+pub const MAX_DEPTH: usize = 2_000;
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Calls `ident` with `args`, an Error from inside a Function gets a Frame
+/// for this call at `span`.
+pub fn eval(ident: Expr, args: Vec<Box<Expr>>, span: Span, env1: Environment) -> IResult {
+    let (ident, mut env2) = eval_expr(ident, env1)?;
     if let Constant::Keyword(name) = ident {
-        match env2.get(&name) {
+        let mut values = Vec::new();
+        for arg in args {
+            let (value, env3) = eval_expr(*arg, env2)?;
+            values.push(value);
+            env2 = env3;
+        }
+        let callee = match env2.get(&name) {
             Some(data_struct) => match data_struct {
-                DataStruct::Function(name, param, stmt) => {
-                    if param.len() != args.len() {
-                        return Err(Error::new(
-                        &format!(
-                            "Miss matched parameter count for Function <{}> Expected {} but found {}",
-                            name, param.len(), args.len()
-                            ),
-                        ErrorKind::MisMatchedPramas,
-                    ));
-                    }
-                    let mut block_env = Environment::new();
-                    for (p, expr) in param.iter().zip(args) {
-                        block_env.insert(p.clone(), DataStruct::Argument(*expr));
+                DataStruct::Function(name, param, stmt)
+                | DataStruct::Prelude(name, param, stmt) => Constant::Function {
+                    name: name.clone(),
+                    params: param.clone(),
+                    body: Box::new(stmt.clone()),
+                    applied: Vec::new(),
+                },
+                DataStruct::Argument(arg, _) => eval_expr(arg.clone(), env2.clone())?.0,
+                DataStruct::Value(name, stmt, value) => match value.get() {
+                    Some(constant) => constant.clone(),
+                    None => {
+                        let function = Constant::Function {
+                            name: name.clone(),
+                            params: Vec::new(),
+                            body: Box::new(stmt.clone()),
+                            applied: Vec::new(),
+                        };
+                        let frame = Frame::new(name, span, 0);
+                        let (constant, _) = apply(function, Vec::new(), env2.clone())
+                            .map_err(|e| e.called(frame))?;
+                        value.get_or_init(|| constant).clone()
                     }
-                    let (result, _) = eval_expr(stmt.clone(), block_env)?;
-                    return Ok((result, env2));
+                },
+            },
+            None => match builtins::lookup(&name) {
+                Some(native) => Constant::Native {
+                    name: native.name.into(),
+                    applied: Vec::new(),
+                },
+                None => {
+                    let names = suggest::similar_names(&name, &env2);
+                    return Err(Error::new(
+                        &format!("{} is not Defined", name),
+                        ErrorKind::Undefined,
+                    )
+                    .with_help(suggest::did_you_mean(&names)));
                 }
-                DataStruct::Argument(arg) => return eval_expr(arg.clone(), env2),
             },
-            None => {
-                return Err(Error::new(
-                    &format!("{} is not Defined", name),
-                    ErrorKind::Undefined,
-                ))
-            }
+        };
+        if let Constant::Function { .. } | Constant::Native { .. } = callee {
+            let frame = Frame::new(&name, span, values.len());
+            return apply(callee, values, env2).map_err(|e| e.called(frame));
         }
+        return apply(callee, values, env2);
     }
     unreachable!()
 }
+
+/// Applies a Function value to arguments.  Too few arguments returns the
+/// partially applied Function and extra arguments are applied to the result.
+pub fn apply(callee: Constant, args: Vec<Constant>, env: Environment) -> IResult {
+    match callee {
+        Constant::Function {
+            name,
+            params,
+            body,
+            mut applied,
+        } => {
+            applied.extend(args);
+            if applied.len() < params.len() {
+                return Ok((
+                    Constant::Function {
+                        name,
+                        params,
+                        body,
+                        applied,
+                    },
+                    env,
+                ));
+            }
+            let rest = applied.split_off(params.len());
+            // The body sees the program's definitions and its own parameters,
+            // never the parameters of whoever called it.
+            let mut block_env: Environment = env
+                .iter()
+                .filter_map(|(name, data_struct)| {
+                    match data_struct {
+                        DataStruct::Argument(_, hidden) => hidden.as_deref().cloned(),
+                        data_struct => Some(data_struct.clone()),
+                    }
+                    .map(|data_struct| (name.clone(), data_struct))
+                })
+                .collect();
+            for (p, value) in params.iter().zip(applied) {
+                let hidden = block_env.remove(p).map(Box::new);
+                block_env.insert(p.clone(), DataStruct::Argument(value.into(), hidden));
+            }
+            let (result, _) = nested(&name, || eval_expr(*body, block_env))?;
+            apply_rest(&name, params.len(), result, rest, env)
+        }
+        Constant::Native { name, mut applied } => {
+            let native = match builtins::lookup(&name) {
+                Some(native) => native,
+                None => {
+                    return Err(Error::new(
+                        &format!("{} is not Defined", name),
+                        ErrorKind::Undefined,
+                    ))
+                }
+            };
+            applied.extend(args);
+            if applied.len() < native.arity {
+                return Ok((Constant::Native { name, applied }, env));
+            }
+            let rest = applied.split_off(native.arity);
+            let result = nested(&name, || (native.func)(&applied, &env))?;
+            apply_rest(&name, native.arity, result, rest, env)
+        }
+        constant if args.is_empty() => Ok((constant, env)),
+        constant => Err(Error::new(
+            &format!(
+                "Can not call a <{}> Type with {} arguments",
+                constant.name(),
+                args.len()
+            ),
+            ErrorKind::MisMatchedPramas,
+        )),
+    }
+}
+
+/// Runs the body of the call to `name`, an Error once `MAX_DEPTH` calls are
+/// already running.
+fn nested<T>(name: &str, body: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    let depth = DEPTH.with(|depth| depth.replace(depth.get() + 1));
+    let result = match depth < MAX_DEPTH {
+        true => body(),
+        false => Err(Error::new(
+            &format!("{} was called more than {} calls deep", name, MAX_DEPTH),
+            ErrorKind::RecursionLimit,
+        )),
+    };
+    DEPTH.with(|depth| depth.set(depth.get() - 1));
+    result
+}
+
+fn apply_rest(
+    name: &str,
+    arity: usize,
+    result: Constant,
+    rest: Vec<Constant>,
+    env: Environment,
+) -> IResult {
+    match result {
+        _ if rest.is_empty() => Ok((result, env)),
+        Constant::Function { .. } | Constant::Native { .. } => apply(result, rest, env),
+        _ => Err(Error::new(
+            &format!(
+                "Miss matched parameter count for Function <{}> Expected {} but found {}",
+                name,
+                arity,
+                arity + rest.len()
+            ),
+            ErrorKind::MisMatchedPramas,
+        )),
+    }
+}
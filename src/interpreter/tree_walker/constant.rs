@@ -1,21 +1,6 @@
-use super::eval_expr;
 use super::{Environment, IResult};
-use crate::parser::{Builtin, Constant};
+use crate::parser::Constant;
 
 pub fn eval(constant: Constant, env1: Environment) -> IResult {
-    match constant {
-        Constant::Builtin(builtin) => match builtin {
-            Builtin::Print(expr) => {
-                let (constant, env2) = eval_expr(*expr, env1)?;
-                print!("{}", constant);
-                Ok((constant, env2))
-            }
-            Builtin::PrintLn(expr) => {
-                let (constant, env2) = eval_expr(*expr, env1)?;
-                println!("{}", constant);
-                Ok((constant, env2))
-            }
-        },
-        _ => Ok((constant, env1)),
-    }
+    Ok((constant, env1))
 }
@@ -1,7 +1,37 @@
+use crate::parser::Span;
+
 #[derive(Debug, Clone)]
 pub struct Error {
-    pub msg: String,
+    pub msg: Box<str>,
     pub kind: ErrorKind,
+    // Where in the source the Error happened, if known.
+    pub span: Option<Span>,
+    // The calls that led to the Error, innermost first.
+    pub trace: Vec<Frame>,
+    // Help for this Error in particular, used over the ErrorKind's help.
+    pub help: Option<Box<str>>,
+}
+
+/// A call that was running when an Error happened.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub name: String,
+    // Where the call was made.
+    pub span: Span,
+    pub args: usize,
+    // How many more times the same call was made recursively.
+    pub repeats: usize,
+}
+
+impl Frame {
+    pub fn new(name: &str, span: Span, args: usize) -> Self {
+        Self {
+            name: name.into(),
+            span,
+            args,
+            repeats: 0,
+        }
+    }
 }
 
 impl Error {
@@ -9,8 +39,34 @@ impl Error {
         Self {
             msg: msg.into(),
             kind,
+            span: None,
+            trace: Vec::new(),
+            help: None,
         }
     }
+
+    pub fn with_help(mut self, help: Option<String>) -> Self {
+        self.help = help.map(String::into_boxed_str);
+        self
+    }
+
+    /// Adds the call the Error came out of to the trace, a call that
+    /// recursed from the same place is counted instead of added again.
+    pub fn called(mut self, frame: Frame) -> Self {
+        match self.trace.last_mut() {
+            Some(last) if last.name == frame.name && last.span == frame.span => last.repeats += 1,
+            _ => self.trace.push(frame),
+        }
+        self
+    }
+
+    /// Sets the Span unless the Error already has one.
+    pub fn at(mut self, span: Span) -> Self {
+        if self.span.is_none() && !span.is_empty() {
+            self.span = Some(span);
+        }
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -22,4 +78,35 @@ pub enum ErrorKind {
     NotImplemented,
     Undefined,
     MisMatchedPramas,
+    ValueError,
+    PermissionDenied,
+    RecursionLimit,
+}
+
+impl ErrorKind {
+    /// A code that stays the same between releases so Errors can be looked up.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::PrefixError => "E0201",
+            Self::TypeError => "E0202",
+            Self::ReturningNothing => "E0203",
+            Self::MutationError => "E0204",
+            Self::NotImplemented => "E0205",
+            Self::Undefined => "E0206",
+            Self::MisMatchedPramas => "E0207",
+            Self::ValueError => "E0208",
+            Self::PermissionDenied => "E0209",
+            Self::RecursionLimit => "E0210",
+        }
+    }
+
+    pub fn help(&self) -> Option<&'static str> {
+        match self {
+            Self::MutationError => Some("functions can not be redefined, give this one a new name"),
+            Self::Undefined => Some("names have to be defined before the line that uses them"),
+            Self::MisMatchedPramas => Some("check how many parameters the function takes"),
+            Self::RecursionLimit => Some("a function that calls itself needs a case that stops"),
+            _ => None,
+        }
+    }
 }
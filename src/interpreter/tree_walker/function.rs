@@ -5,11 +5,17 @@ use super::{
 use crate::interpreter::DataStruct;
 use crate::parser::{Constant, Expr};
 pub fn eval(name: String, param: Vec<String>, stmt: Expr, mut env: Environment) -> IResult {
-    if env.contains_key(&name) {
+    if let Some(DataStruct::Function(..) | DataStruct::Argument(..) | DataStruct::Value(..)) =
+        env.get(&name)
+    {
         return mutation_error(&name);
     }
-
-    env.insert(name.clone(), DataStruct::Function(name, param, stmt));
+    // Without parameters it is a value, so side effects in it happen once.
+    let data_struct = match param.is_empty() {
+        true => DataStruct::Value(name.clone(), stmt, Default::default()),
+        false => DataStruct::Function(name.clone(), param, stmt),
+    };
+    env.insert(name, data_struct);
     Ok((Constant::Int(0), env))
 }
 
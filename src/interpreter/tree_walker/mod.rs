@@ -2,67 +2,201 @@ mod binary;
 mod call;
 mod conditional;
 mod constant;
-mod error;
+pub mod error;
 mod function;
 mod logic;
+mod suggest;
 mod unary;
 
+pub use call::apply;
+
 use super::Environment;
-use crate::parser::{Constant, Expr};
+use crate::parser::{Constant, Expr, ExprKind, Span};
 use error::{Error, ErrorKind};
 
 type IResult = Result<(Constant, Environment), Error>;
 
-pub fn eval_expr(expr: Expr, mut env: Environment) -> IResult {
-    match expr {
-        Expr::Constant(constant) => constant::eval(constant, env),
-        Expr::Unary { op, child } => {
+/// Evaluates `expr`, an Error without a Span is given the Span of `expr`.
+pub fn eval_expr(expr: Expr, env: Environment) -> IResult {
+    let span = expr.span;
+    eval_kind(expr.kind, span, env).map_err(|e| e.at(span))
+}
+
+fn eval_kind(kind: ExprKind, span: Span, mut env: Environment) -> IResult {
+    match kind {
+        ExprKind::Constant(constant) => constant::eval(constant, env),
+        ExprKind::Unary { op, child } => {
             let (result, e) = unary::eval(op, *child, env)?;
             env = e;
             Ok((result, env))
         }
-        Expr::Binary { op, lhs, rhs } => {
+        ExprKind::Binary { op, lhs, rhs } => {
             let (result, e) = binary::eval(op, *lhs, *rhs, env)?;
             env = e;
             Ok((result, env))
         }
 
-        Expr::If(condision, statement) => {
+        ExprKind::If(condision, statement) => {
             let (result, e) = conditional::eval_if(*condision, *statement, env)?;
             env = e;
             Ok((result, env))
         }
-        Expr::IfElse(condision, stmt, else_stmt) => {
+        ExprKind::IfElse(condision, stmt, else_stmt) => {
             let (result, e) = conditional::eval_if_else(*condision, *stmt, *else_stmt, env)?;
             env = e;
             Ok((result, env))
         }
-        Expr::And(lhs, rhs) => {
+        ExprKind::And(lhs, rhs) => {
             let (result, e) = logic::eval_and(*lhs, *rhs, env)?;
             env = e;
             Ok((result, env))
         }
-        Expr::Or(lhs, rhs) => {
+        ExprKind::Or(lhs, rhs) => {
             let (result, e) = logic::eval_or(*lhs, *rhs, env)?;
             env = e;
             Ok((result, env))
         }
-        Expr::Function(name, param, stmt) => {
+        ExprKind::Function(name, param, stmt, _) => {
             let (result, e) = function::eval(name, param, *stmt, env)?;
             env = e;
             Ok((result, env))
         }
-        Expr::Call(keyword, args) => {
-            let (result, e) = call::eval(*keyword, args, env)?;
+        ExprKind::Call(keyword, args) => {
+            let (result, e) = call::eval(*keyword, args, span, env)?;
             env = e;
             Ok((result, env))
         }
-        expr => Err(Error::new(
+        ExprKind::List(items) => {
+            let mut list = Vec::new();
+            for item in items {
+                let (result, e) = eval_expr(item, env)?;
+                list.push(result);
+                env = e;
+            }
+            Ok((Constant::List(list), env))
+        }
+        ExprKind::Sequence(singles) => {
+            let mut result = Constant::Null;
+            for single in singles {
+                let (value, e) = eval_expr(single, env)?;
+                result = value;
+                env = e;
+            }
+            Ok((result, env))
+        }
+        kind => Err(Error::new(
             &format!(
                 "You have not implemented this {:?} Expression in the Interrupter",
-                expr
+                kind
             ),
             ErrorKind::NotImplemented,
         )),
     }
 }
+
+#[test]
+fn errors_have_spans() {
+    use crate::parser::{parser, Position};
+    let (_, exprs) = parser("x = 1\nx + \"one\"").unwrap();
+    let mut exprs = exprs.into_iter();
+    let (_, env) = eval_expr(exprs.next().unwrap(), Environment::new()).unwrap();
+    let error = eval_expr(exprs.next().unwrap(), env).unwrap_err();
+    assert_eq!(
+        error.span,
+        Some(Span::new(Position::new(2, 1, 6), Position::new(2, 10, 15)))
+    );
+}
+
+#[test]
+fn errors_have_traces() {
+    use crate::parser::{parser, Position};
+    let source = "count n = if n == 0 then missing else count (n - 1)\ncount 3";
+    let (_, exprs) = parser(source).unwrap();
+    let mut exprs = exprs.into_iter();
+    let (_, env) = eval_expr(exprs.next().unwrap(), Environment::new()).unwrap();
+    let error = eval_expr(exprs.next().unwrap(), env).unwrap_err();
+    let trace: Vec<_> = error
+        .trace
+        .iter()
+        .map(|frame| {
+            (
+                frame.name.as_str(),
+                frame.span.start,
+                frame.args,
+                frame.repeats,
+            )
+        })
+        .collect();
+    assert_eq!(
+        trace,
+        vec![
+            ("count", Position::new(1, 39, 38), 1, 2),
+            ("count", Position::new(2, 1, 52), 1, 0),
+        ]
+    );
+}
+
+#[test]
+fn sequence_is_its_last_value() {
+    use crate::parser::parser;
+    let (_, exprs) = parser("twice n = n; n * 2\n(twice 4; twice 5) + 1").unwrap();
+    let mut exprs = exprs.into_iter();
+    let (_, env) = eval_expr(exprs.next().unwrap(), Environment::new()).unwrap();
+    let (value, _) = eval_expr(exprs.next().unwrap(), env).unwrap();
+    assert_eq!(value, Constant::Int(11));
+}
+
+#[test]
+fn functions_do_not_see_callers_parameters() {
+    use crate::parser::parser;
+    let (_, exprs) = parser("n = 10\nshow x = n + x\nwrap n = show 1\nwrap 5").unwrap();
+    let mut env = Environment::new();
+    let mut value = Constant::Null;
+    for expr in exprs {
+        (value, env) = eval_expr(expr, env).unwrap();
+    }
+    assert_eq!(value, Constant::Int(11));
+}
+
+#[test]
+fn runaway_recursion_is_an_error() {
+    use crate::parser::parser;
+    let error = crate::interpreter::with_stack(|| {
+        let (_, exprs) = parser("loop x = loop x\nloop 1").unwrap();
+        let mut exprs = exprs.into_iter();
+        let (_, env) = eval_expr(exprs.next().unwrap(), Environment::new()).unwrap();
+        eval_expr(exprs.next().unwrap(), env).unwrap_err()
+    });
+    assert!(matches!(error.kind, ErrorKind::RecursionLimit));
+    let repeats: Vec<_> = error.trace.iter().map(|frame| frame.repeats).collect();
+    assert_eq!(repeats, vec![call::MAX_DEPTH - 1, 0]);
+}
+
+#[test]
+fn values_are_worked_out_once() {
+    use crate::parser::parser;
+    let source = "x = random_int 1 1000000000\nsame = x == x\ncheck n = x == x\n[same, check 1]";
+    let (_, exprs) = parser(source).unwrap();
+    let mut env = Environment::new();
+    let mut value = Constant::Null;
+    for expr in exprs {
+        (value, env) = eval_expr(expr, env).unwrap();
+    }
+    let yes = Constant::Boolean(true);
+    assert_eq!(value, Constant::List(vec![yes.clone(), yes]));
+}
+
+#[test]
+fn functions_called_by_natives_are_in_the_trace() {
+    use crate::parser::parser;
+    let (_, exprs) = parser("bad x = x + \"a\"\nmap bad [1]").unwrap();
+    let mut exprs = exprs.into_iter();
+    let (_, env) = eval_expr(exprs.next().unwrap(), Environment::new()).unwrap();
+    let error = eval_expr(exprs.next().unwrap(), env).unwrap_err();
+    let names: Vec<_> = error
+        .trace
+        .iter()
+        .map(|frame| frame.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["bad", "map"]);
+}
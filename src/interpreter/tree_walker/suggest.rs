@@ -0,0 +1,89 @@
+/*
+ * tree_walker/suggest.rs
+ *
+ * "Did you mean" suggestions for names that are not defined.
+ */
+use super::Environment;
+use crate::interpreter::builtins;
+use crate::parser::KEYWORDS;
+
+/// The defined names closest to `name`, best first.
+pub fn similar_names(name: &str, env: &Environment) -> Vec<String> {
+    let candidates = env
+        .keys()
+        .map(String::as_str)
+        .chain(builtins::registry().names().map(|name| name as &str))
+        .chain(KEYWORDS.iter().copied());
+    closest(name, candidates)
+}
+
+/// Help text naming the closest of `names`.
+pub fn did_you_mean(names: &[String]) -> Option<String> {
+    let quoted: Vec<_> = names.iter().map(|name| format!("`{}`", name)).collect();
+    match quoted.as_slice() {
+        [] => None,
+        [one] => Some(format!("did you mean {}?", one)),
+        [rest @ .., last] => Some(format!("did you mean {} or {}?", rest.join(", "), last)),
+    }
+}
+
+// Up to three candidates within a third of the length of `name`, that keep
+// at least one character of both, so `x` is not taken for `e`.
+fn closest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let length = name.chars().count();
+    let limit = (length / 3).max(1);
+    let mut found: Vec<(usize, &str)> = candidates
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (distance(name, candidate), candidate))
+        .filter(|(distance, candidate)| {
+            *distance <= limit && *distance < length.min(candidate.chars().count())
+        })
+        .collect();
+    found.sort_unstable();
+    found.dedup();
+    found
+        .into_iter()
+        .take(3)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+/// Edits to turn `a` into `b`, swapping two neighbouring characters is one edit.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
+#[test]
+fn suggestions() {
+    assert_eq!(distance("prnt", "print"), 1);
+    assert_eq!(distance("lenght", "length"), 1);
+    assert_eq!(distance("", "abc"), 3);
+    let names = ["print", "println", "split", "if"].into_iter();
+    assert_eq!(closest("prnt", names.clone()), vec!["print"]);
+    assert_eq!(closest("iff", names.clone()), vec!["if"]);
+    assert!(closest("zzz", names).is_empty());
+    let short = ["e", "pi", "xs"].into_iter();
+    assert!(closest("x", short.clone()).is_empty());
+    assert!(closest("p", short.clone()).is_empty());
+    assert_eq!(closest("ys", short), vec!["xs"]);
+    assert_eq!(
+        did_you_mean(&["a".into(), "b".into(), "c".into()]),
+        Some("did you mean `a`, `b` or `c`?".into())
+    );
+}
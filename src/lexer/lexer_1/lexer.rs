@@ -1,26 +1,18 @@
 // use crate::token::Token;
-use crate::lexer::token::{KeyWord, Position, Span, Spanned, SpannedToken, Token};
+use super::token::{KeyWord, Position, Span, Spanned, SpannedToken, Token};
 use std::iter::Peekable;
 
+#[derive(Default)]
 pub struct Lexer {
     pos: Position,
     pub tokens: Vec<SpannedToken>,
 }
 
-impl Default for Lexer {
-    fn default() -> Self {
-        Self {
-            pos: Position::default(),
-            tokens: Vec::new(),
-        }
-    }
-}
-
 impl Lexer {
     pub fn lex(&mut self, stream: &[char]) {
         if stream.is_empty() {
             self.add_token(Token::EOF);
-            return ();
+            return;
         }
         let mut stream = stream.iter().peekable();
 
@@ -37,7 +29,7 @@ impl Lexer {
                 '=' if stream.peek() == Some(&&'=') => self.add_token(Token::EqualEqual),
                 '=' => self.add_token(Token::Equal),
                 '+' => self.add_token(Token::Plus),
-                '-' if stream.peek().map_or(false, |c| c.is_numeric()) => {
+                '-' if stream.peek().is_some_and(|c| c.is_numeric()) => {
                     self.add_number(c, &mut stream)
                 }
                 '-' => self.add_token(Token::Minus),
@@ -68,7 +60,7 @@ impl Lexer {
     }
 
     fn commit(&mut self, stream: &mut Peekable<std::slice::Iter<'_, char>>) {
-        while let Some(c) = stream.next() {
+        for c in stream.by_ref() {
             self.pos.right_shift();
             if c == &'\n' {
                 self.pos.new_line();
@@ -87,7 +79,7 @@ impl Lexer {
 
     fn add_number(&mut self, c: &char, stream: &mut Peekable<std::slice::Iter<'_, char>>) {
         let mut number = c.to_string();
-        let start = self.pos.clone();
+        let start = self.pos;
         while let Some(c) =
             stream.next_if(|&c| c.is_numeric() || (c == &'.' && !number.contains('.')))
         {
@@ -108,7 +100,7 @@ impl Lexer {
 
     fn add_identifier(&mut self, c: &char, stream: &mut Peekable<std::slice::Iter<'_, char>>) {
         let mut idt = c.to_string();
-        let start = self.pos.clone();
+        let start = self.pos;
         while let Some(c) = stream.next_if(|&c| c.is_ascii_alphabetic()) {
             idt.push(*c);
             self.pos.right_shift();
@@ -123,7 +115,7 @@ impl Lexer {
 
     fn add_string(&mut self, stream: &mut Peekable<std::slice::Iter<'_, char>>) {
         let mut string = String::new();
-        let start = self.pos.clone();
+        let start = self.pos;
         while let Some(c) = stream.next() {
             if c == &'"' {
                 let _ = stream.next();
@@ -1,4 +1,4 @@
 mod lexer;
 mod token;
 pub use lexer::Lexer;
-pub use token::{KeyWord, Span, SpannedToken, Token};
+pub use token::{KeyWord, Position, Span, SpannedToken, Token};
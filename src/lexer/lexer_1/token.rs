@@ -1,4 +1,7 @@
-#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Copy, Clone)]
+/// A place in the source.  The nom parser counts `line` and `column` from 1
+/// and `idx` in bytes with `advance`, the hand written lexer counts from 0
+/// and `idx` in chars with `right_shift` and `new_line`.
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Debug, Default, Copy, Clone)]
 pub struct Position {
     pub line: usize,
     pub column: usize,
@@ -21,6 +24,20 @@ impl Position {
         self.idx += 1;
     }
 
+    /// The Position after reading `text` from here.
+    pub fn advance(self, text: &str) -> Self {
+        text.chars().fold(self, |mut pos, c| {
+            pos.idx += c.len_utf8();
+            if c == '\n' {
+                pos.line += 1;
+                pos.column = 1;
+            } else {
+                pos.column += 1;
+            }
+            pos
+        })
+    }
+
     pub fn into_span(self) -> Span {
         Span {
             start: self,
@@ -29,17 +46,9 @@ impl Position {
     }
 }
 
-impl Default for Position {
-    fn default() -> Self {
-        Self {
-            line: 0,
-            column: 0,
-            idx: 0,
-        }
-    }
-}
-
-#[derive(PartialEq, PartialOrd, Debug, Copy, Clone)]
+/// The source between two Positions, `end` is just past the last character.
+/// Expressions built outside of the parser have the default empty Span.
+#[derive(PartialEq, PartialOrd, Eq, Hash, Debug, Default, Copy, Clone)]
 pub struct Span {
     pub start: Position,
     pub end: Position,
@@ -49,6 +58,15 @@ impl Span {
     pub fn new(start: Position, end: Position) -> Self {
         Self { start, end }
     }
+
+    /// A Span from the start of `self` to the end of `other`.
+    pub fn to(self, other: Self) -> Self {
+        Self::new(self.start, other.end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -135,10 +153,9 @@ impl Token {
         // Check if the keywords are the same
         // Two tokens can be the same variant, but have different inner values
         // We especially care about the Keyword value however
-        match (&self, tok) {
-            (Token::KeyWord(x), Token::KeyWord(y)) => return x == y,
-            (_, _) => {}
-        };
+        if let (Token::KeyWord(x), Token::KeyWord(y)) = (&self, tok) {
+            return x == y;
+        }
 
         // If we arrive here, then the Token variants are the same and they are not the Keyword type
         same_token_variant
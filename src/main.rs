@@ -1,14 +1,50 @@
+mod diagnostic;
+mod error;
 mod files;
 mod interpreter;
+mod lexer;
 mod parser;
+mod prelude;
 mod shell;
 
+use interpreter::{Environment, Permissions};
+
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
+    interpreter::with_stack(run);
+}
+
+fn run() {
+    let mut args: Vec<String> = std::env::args().collect();
+    let script_args = split_script_args(&mut args);
+    let no_prelude = take_flag(&mut args, "--no-prelude");
+    let mut permissions = Permissions::default();
+    args.retain(|arg| !permissions.parse_flag(arg));
+    let is_repl = matches!(args.get(1).map(String::as_str), Some("repl" | "-s"));
+    if is_repl && permissions == Permissions::default() {
+        permissions = Permissions::all();
+    }
+    interpreter::set_permissions(permissions);
+    if let Some(seed) = take_seed(&mut args) {
+        interpreter::set_seed(seed);
+    }
+    let env = if no_prelude {
+        Environment::new()
+    } else {
+        prelude::load()
+    };
     match args.get(1).unwrap_or(&String::new()).as_str() {
-        "repl" | "-s" => shell::run().expect("something went wrong with rustyline"),
-        "run" | "-r" if args.len() == 3 => files::run(&args[2]),
-        "run" | "-r" => println!("'run' command takes one file argument."),
+        "repl" | "-s" => shell::run(env).expect("something went wrong with rustyline"),
+        "run" | "-r" if args.len() == 3 => files::run(&args[2], &script_args, env),
+        "run" | "-r" => {
+            println!("'run' command takes a file argument.");
+            std::process::exit(64);
+        }
+        "-n" if args.len() == 3 => files::run_lines(&args[2], false, env),
+        "-p" if args.len() == 3 => files::run_lines(&args[2], true, env),
+        "-n" | "-p" => {
+            println!("'{}' takes one expression argument.", args[1]);
+            std::process::exit(64);
+        }
         "--help" | "-h" => command_line_help(),
         _ => {
             println!("Usage: rlang [path]");
@@ -17,14 +53,60 @@ fn main() {
     }
 }
 
+/// Splits off everything after the script file so the script's own
+/// arguments are not read as flags.
+fn split_script_args(args: &mut Vec<String>) -> Vec<String> {
+    let mut positional = args
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, arg)| !arg.starts_with("--"))
+        .map(|(i, _)| i);
+    match (positional.next(), positional.next()) {
+        (Some(_), Some(file)) => args.split_off(file + 1),
+        _ => Vec::new(),
+    }
+}
+
+/// Removes `flag` from `args` returning true if it was there.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let found = args.iter().any(|arg| arg == flag);
+    args.retain(|arg| arg != flag);
+    found
+}
+
+/// Removes `--seed=N` from `args`, exiting when N is not a number.
+fn take_seed(args: &mut Vec<String>) -> Option<u64> {
+    let index = args.iter().position(|arg| arg.starts_with("--seed="))?;
+    let arg = args.remove(index);
+    match arg["--seed=".len()..].parse() {
+        Ok(seed) => Some(seed),
+        Err(_) => {
+            println!("'--seed' takes a number, found {:?}.", arg);
+            std::process::exit(64);
+        }
+    }
+}
+
 fn command_line_help() {
     println!(
         "lite lang ARGS:
 verison 0.1.0
 
 repl   | -s        : Runs the interactive Repl.
-run    | -r [FILE] : Run takes a file and runs it.
+run    | -r [FILE] [ARGS..] : Run takes a file and runs it with `args` bound to ARGS.
+-n [EXPR]          : Evaluate EXPR for every line of stdin with `line` bound.
+-p [EXPR]          : Like -n but prints the result for every line.
 --help | -h        : Display this help message.
+--no-prelude       : Start without loading prelude.uln.
+--seed=N           : Seed `random_int`, `random_float` and `shuffle`.
+
+Permissions, everything is allowed in the Repl unless one is given:
+--allow-read[=PATHS]  : Allow reading files, only under PATHS when given.
+--allow-write[=PATHS] : Allow writing files, only under PATHS when given.
+--allow-exec          : Allow `exec` and `exec_stream` to run programs.
+--allow-env           : Allow reading environment variables.
+--allow-all           : Allow everything.
 "
     );
 }
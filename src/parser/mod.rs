@@ -1,5 +1,6 @@
 mod node;
 mod nom_parser;
 
-pub use node::{Builtin, Constant, Expr, Operator};
+pub use node::{Constant, Expr, ExprKind, Operator, Position, Regex, Span};
 pub use nom_parser::parser;
+pub(crate) use nom_parser::KEYWORDS;
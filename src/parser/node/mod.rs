@@ -1,2 +1,3 @@
 mod node;
-pub use node::{Builtin, Constant, Expr, Operator};
+pub use crate::lexer::{Position, Span};
+pub use node::{Constant, Expr, ExprKind, Operator, Regex};
@@ -1,32 +1,60 @@
+use crate::lexer::Span;
+use std::collections::BTreeMap;
 use std::fmt;
-#[derive(Debug, PartialEq, Clone)]
-pub enum Builtin {
-    Print(Box<Expr>),
-    PrintLn(Box<Expr>),
+
+/// A compiled regular expression, equal to another with the same pattern.
+#[derive(Debug, Clone)]
+pub struct Regex(pub regex::Regex);
+
+impl PartialEq for Regex {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Constant {
+    Null,
     String(String),
     Int(i128),
     Float(f64),
     Boolean(bool),
     Keyword(String),
-    Builtin(Builtin),
+    List(Vec<Constant>),
+    Record(BTreeMap<String, Constant>),
+    Regex(Regex),
+    // name parameter statement and the arguments applied so far.
+    Function {
+        name: String,
+        params: Vec<String>,
+        body: Box<Expr>,
+        applied: Vec<Constant>,
+    },
+    // name of a registered native function and the arguments applied so far.
+    Native {
+        name: String,
+        applied: Vec<Constant>,
+    },
+    // Result of a Native Function that can fail.
+    Ok(Box<Constant>),
+    Err(Box<Constant>),
 }
 
 impl Constant {
     pub fn name(&self) -> &str {
         match self {
+            Self::Null => "Null",
             Self::String(_) => "String",
             Self::Int(_) => "Int",
             Self::Float(_) => "Float",
-            Self::Boolean(_) => "Float",
+            Self::Boolean(_) => "Boolean",
             Self::Keyword(_) => "Keyword",
-            Self::Builtin(b) => match b {
-                Builtin::Print(_) => "Builtin Function Print",
-                Builtin::PrintLn(_) => "Builtin Function PrintLn",
-            },
+            Self::List(_) => "List",
+            Self::Record(_) => "Record",
+            Self::Regex(_) => "Regex",
+            Self::Function { .. } => "Function",
+            Self::Native { .. } => "Native Function",
+            Self::Ok(_) | Self::Err(_) => "Result",
         }
     }
 }
@@ -34,12 +62,48 @@ impl Constant {
 impl fmt::Display for Constant {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::Null => write!(f, "null"),
             Self::String(s) => write!(f, "{}", s),
             Self::Int(i) => write!(f, "{}", i),
             Self::Float(float) => write!(f, "{}", float),
             Self::Boolean(b) => write!(f, "{}", b),
             Self::Keyword(s) => write!(f, "{}", s),
-            Self::Builtin(_) => write!(f, "Print"),
+            Self::List(list) => {
+                write!(f, "[")?;
+                for (i, item) in list.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    item.fmt_item(f)?;
+                }
+                write!(f, "]")
+            }
+            Self::Record(record) => {
+                write!(f, "{{")?;
+                for (i, (key, item)) in record.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: ", key)?;
+                    item.fmt_item(f)?;
+                }
+                write!(f, "}}")
+            }
+            Self::Regex(regex) => write!(f, "/{}/", regex.0.as_str()),
+            Self::Function { name, .. } => write!(f, "<function {}>", name),
+            Self::Native { name, .. } => write!(f, "<native {}>", name),
+            Self::Ok(c) => write!(f, "Ok({})", c),
+            Self::Err(c) => write!(f, "Err({})", c),
+        }
+    }
+}
+
+impl Constant {
+    // Strings inside a List or Record are quoted.
+    fn fmt_item(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(s) => write!(f, "{:?}", s),
+            item => write!(f, "{}", item),
         }
     }
 }
@@ -92,39 +156,98 @@ impl Operator {
     }
 }
 
+/// An expression and where it came from.  Two Expr are equal when they have
+/// the same shape no matter where they are in the source.
+#[derive(Debug, Clone)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    /// Forgets where this Expr and everything in it came from, for code that
+    /// is not part of the program being run.
+    pub fn clear_spans(&mut self) {
+        self.span = Span::default();
+        match &mut self.kind {
+            ExprKind::Constant(_) | ExprKind::Error { .. } => {}
+            ExprKind::Unary { child, .. } => child.clear_spans(),
+            ExprKind::Binary { lhs, rhs, .. }
+            | ExprKind::If(lhs, rhs)
+            | ExprKind::And(lhs, rhs)
+            | ExprKind::Or(lhs, rhs) => {
+                lhs.clear_spans();
+                rhs.clear_spans();
+            }
+            ExprKind::IfElse(condition, stmt, else_stmt) => {
+                condition.clear_spans();
+                stmt.clear_spans();
+                else_stmt.clear_spans();
+            }
+            ExprKind::Function(_, _, body, _) => body.clear_spans(),
+            ExprKind::Call(callee, args) => {
+                callee.clear_spans();
+                args.iter_mut().for_each(|arg| arg.clear_spans());
+            }
+            ExprKind::List(items) | ExprKind::Sequence(items) => {
+                items.iter_mut().for_each(Expr::clear_spans)
+            }
+        }
+    }
+}
+
+impl PartialEq for Expr {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
-pub enum Expr {
+pub enum ExprKind {
     Constant(Constant),
     Unary {
         op: Operator,
-        child: Box<Self>,
+        child: Box<Expr>,
     },
     Binary {
         op: Operator,
-        lhs: Box<Self>,
-        rhs: Box<Self>,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
     },
     // condision  statement
-    If(Box<Self>, Box<Self>),
+    If(Box<Expr>, Box<Expr>),
     // condision  statement else statement
-    IfElse(Box<Self>, Box<Self>, Box<Self>),
-    And(Box<Self>, Box<Self>),
-    Or(Box<Self>, Box<Self>),
-    // name parameter statement
+    IfElse(Box<Expr>, Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    // name parameter statement doc
     // TODO: FIXME: First argument in Function Expression needs
     // to be a Constant.
-    Function(String, Vec<String>, Box<Self>),
-    Call(Box<Self>, Vec<Box<Self>>),
+    Function(String, Vec<String>, Box<Expr>, Option<String>),
+    Call(Box<Expr>, Vec<Box<Expr>>),
+    List(Vec<Expr>),
+    // statement ; statement ...  the value is the last one's.
+    Sequence(Vec<Expr>),
+    // Source the parser skipped after a syntax error, what was expected
+    // and the grammar rules it was in.
+    Error {
+        label: String,
+        notes: Vec<String>,
+    },
 }
 
-impl From<Constant> for Expr {
-    fn from(c: Constant) -> Self {
-        Self::Constant(c)
+impl From<ExprKind> for Expr {
+    fn from(kind: ExprKind) -> Self {
+        Self::new(kind, Span::default())
     }
 }
 
-impl From<Builtin> for Expr {
-    fn from(b: Builtin) -> Self {
-        Self::Constant(Constant::Builtin(b))
+impl From<Constant> for Expr {
+    fn from(c: Constant) -> Self {
+        ExprKind::Constant(c).into()
     }
 }
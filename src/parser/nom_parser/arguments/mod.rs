@@ -3,15 +3,81 @@
  *
  * Handles Argument's for Calling DataStruct's Grammer
  */
-use super::{expression, Expr};
+use super::{
+    common::{ends_line, Input},
+    primary, Constant, Expr, ExprKind,
+};
 
-use nom::{combinator::map, error::VerboseError, multi::many0, IResult};
+use nom::{error::VerboseError, IResult};
 
-// arguments → expression ( expression)* ;
-pub fn parser(input: &str) -> IResult<&str, Vec<Box<Expr>>, VerboseError<&str>> {
+// arguments → primary ( primary )* ;
+// Arguments stop at the end of the line or at anything that is not a
+// primary, a primary that fails part way through is an Error.
+pub fn parser(input: Input) -> IResult<Input, Vec<Box<Expr>>, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for arguments");
     }
-    let (i, args) = many0(map(expression::parser, Box::new))(input)?;
+    let mut args = Vec::new();
+    let mut i = input;
+    loop {
+        let (rest, expr) = match primary::parser(i) {
+            Ok(parsed) => parsed,
+            Err(nom::Err::Error(_)) => break,
+            Err(e) => return Err(e),
+        };
+        args.push(argument(expr));
+        let done = ends_line(i, rest);
+        i = rest;
+        if done {
+            break;
+        }
+    }
     Ok((i, args))
 }
+
+// A bare IDENTIFIER argument is a reference to a DataStruct, not a Keyword.
+fn argument(expr: Expr) -> Box<Expr> {
+    match expr.kind {
+        ExprKind::Constant(Constant::Keyword(_)) => {
+            let span = expr.span;
+            Box::new(Expr::new(ExprKind::Call(Box::new(expr), vec![]), span))
+        }
+        _ => Box::new(expr),
+    }
+}
+
+#[test]
+fn arguments_parse() {
+    use super::common::parse;
+    let data = "double [1, 2] 3";
+    assert_eq!(
+        parse(parser, data),
+        Ok((
+            "",
+            vec![
+                Box::new(Expr::from(ExprKind::Call(
+                    Box::new(Constant::Keyword("double".into()).into()),
+                    vec![]
+                ))),
+                Box::new(Expr::from(ExprKind::List(vec![
+                    Constant::Int(1).into(),
+                    Constant::Int(2).into()
+                ]))),
+                Box::new(Constant::Int(3).into()),
+            ]
+        ))
+    );
+    assert_eq!(
+        parse(parser, "1\nnext"),
+        Ok(("next", vec![Box::new(Constant::Int(1).into())]))
+    );
+}
+
+#[test]
+fn arguments_keep_failures() {
+    use super::common::parse;
+    match parse(parser, "(1 ;) 2") {
+        Err(nom::Err::Failure(error)) => assert_eq!(error.errors[0].0.get_utf8_column(), 4),
+        result => panic!("expected a Failure found {:?}", result),
+    }
+}
@@ -3,7 +3,10 @@
  *
  * Handles all Block in Grammer.
  */
-use super::{common::trim, statement, Expr};
+use super::{
+    common::{trim, Input},
+    statement, Expr,
+};
 
 use nom::{
     bytes::complete::tag,
@@ -14,7 +17,7 @@ use nom::{
 
 // ❌   block → "{" declaration* "}"
 // ❌   block → "{" statement* "}"
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for block");
     }
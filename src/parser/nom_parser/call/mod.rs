@@ -3,19 +3,59 @@
  *
  * Handles Calling Grammer
  */
-use super::{arguments, primary, Constant, Expr};
+use super::{
+    arguments,
+    common::{ends_line, Input},
+    primary, Constant, Expr, ExprKind,
+};
 
 use nom::{error::VerboseError, IResult};
 
+#[cfg(test)]
+use super::common::parse;
+
 // call → primary ( arguments? )* ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for a Call");
     }
     let (i1, ident) = primary::parser(input)?;
-    if let Expr::Constant(Constant::Keyword(_)) = ident {
-        let (i2, args) = arguments::parser(i1)?;
-        return Ok((i2, Expr::Call(Box::new(ident), args)));
+    if let ExprKind::Constant(Constant::Keyword(_)) = ident.kind {
+        // An IDENTIFIER at the end of a line is still a Call, with no arguments.
+        let (i2, args) = match ends_line(input, i1) {
+            true => (i1, Vec::new()),
+            false => arguments::parser(i1)?,
+        };
+        let span = match args.last() {
+            Some(arg) => ident.span.to(arg.span),
+            None => ident.span,
+        };
+        return Ok((i2, Expr::new(ExprKind::Call(Box::new(ident), args), span)));
     }
     Ok((i1, ident))
 }
+
+#[test]
+fn calls_bind_tighter_than_operators() {
+    use super::{expression, Operator};
+    // (f a) + b, (f 2) > 1, (len xs) == 0 and (f 1) - 1
+    for (source, op) in [
+        ("f a + b", Operator::Plus),
+        ("f 2 > 1", Operator::GreaterThen),
+        ("len xs == 0", Operator::Equality),
+        ("f 1 -1", Operator::Minus),
+    ] {
+        let (rest, expr) = parse(expression::parser, source).unwrap();
+        assert_eq!(rest, "");
+        match expr.kind {
+            ExprKind::Binary { op: found, lhs, .. } => {
+                assert_eq!(found, op, "{}", source);
+                assert!(matches!(lhs.kind, ExprKind::Call(_, ref args) if args.len() == 1));
+            }
+            kind => panic!("expected Binary for {} found {:?}", source, kind),
+        }
+    }
+    let (rest, expr) = parse(super::statement::parser, "if len xs == 0 then 1 else 2").unwrap();
+    assert_eq!(rest, "");
+    assert!(matches!(expr.kind, ExprKind::IfElse(..)));
+}
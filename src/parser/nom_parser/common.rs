@@ -1,44 +1,227 @@
+use super::{Expr, ExprKind, Operator, Position, Span};
 use nom::{
-    bytes::complete::{tag, take_until},
-    character::complete::multispace0,
-    error::ParseError,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, multispace0, multispace1, not_line_ending},
+    combinator::{map, opt},
+    error::{ErrorKind, ParseError, VerboseError, VerboseErrorKind},
     multi::many0,
-    sequence::delimited,
-    sequence::preceded,
-    sequence::tuple,
-    IResult,
+    sequence::{delimited, preceded},
+    IResult, InputTake,
 };
+use nom_locate::LocatedSpan;
 
-/// A combinator that takes a parser `inner` and produces a parser that also consumes both leading and
-/// trailing whitespace, and language comments starting with '//' returning the output of `inner`.
-pub(crate) fn trim<'a, F: 'a, O, E: ParseError<&'a str>>(
-    inner: F,
-) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+/// Parser input that knows where it is in the source.
+pub(crate) type Input<'a> = LocatedSpan<&'a str>;
+
+/// Words that can not be used as an IDENTIFIER.
+pub(crate) const KEYWORDS: &[&str] = &["if", "then", "else", "and", "or", "true", "false"];
+
+pub(crate) fn is_keyword(word: &str) -> bool {
+    KEYWORDS.contains(&word)
+}
+
+/// The text consumed between `input` and `rest`.
+pub(crate) fn consumed<'a>(input: Input<'a>, rest: Input<'a>) -> &'a str {
+    &input.fragment()[..rest.location_offset() - input.location_offset()]
+}
+
+/// True when the text consumed between `input` and `rest` ended with a newline.
+pub(crate) fn ends_line(input: Input, rest: Input) -> bool {
+    let consumed = consumed(input, rest);
+    consumed[consumed.trim_end().len()..].contains('\n')
+}
+
+pub(crate) fn position(input: Input) -> Position {
+    Position::new(
+        input.location_line() as usize,
+        input.get_utf8_column(),
+        input.location_offset(),
+    )
+}
+
+/// Skips whitespace and comments returning the Position of what comes next.
+pub(crate) fn start(input: Input) -> IResult<Input, Position, VerboseError<Input>> {
+    let (i, _) = skip(input)?;
+    Ok((i, position(i)))
+}
+
+/// Skips whitespace and comments returning the text of the "///" doc
+/// comments right before what comes next, one line each.
+pub(crate) fn doc_comments(input: Input) -> IResult<Input, Option<String>, VerboseError<Input>> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut i = input;
+    loop {
+        let (rest, _) = multispace0(i)?;
+        if let Ok((rest, text)) = preceded(tag("///"), not_line_ending::<_, ()>)(rest) {
+            lines.push(text.fragment().strip_prefix(' ').unwrap_or(&text).into());
+            i = rest;
+            continue;
+        }
+        match comment(rest) {
+            Ok((rest, _)) => {
+                lines.clear();
+                i = rest;
+            }
+            Err(nom::Err::Error(_)) => {
+                let doc = (!lines.is_empty()).then(|| lines.join("\n"));
+                return Ok((rest, doc));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// A parser that `furthest` can choose between.
+pub(crate) type Choice<'p, 'a, O> =
+    &'p mut dyn FnMut(Input<'a>) -> IResult<Input<'a>, O, VerboseError<Input<'a>>>;
+
+/// Like `alt` but when every parser fails it keeps the Error that got
+/// furthest into the input, `alt` keeps the last parser's wherever it was.
+pub(crate) fn furthest<'a, O>(
+    input: Input<'a>,
+    parsers: &mut [Choice<'_, 'a, O>],
+) -> IResult<Input<'a>, O, VerboseError<Input<'a>>> {
+    let offset = |error: &VerboseError<Input>| {
+        error
+            .errors
+            .first()
+            .map_or(0, |(input, _)| input.location_offset())
+    };
+    let mut furthest: Option<VerboseError<Input>> = None;
+    for parser in parsers {
+        match parser(input) {
+            Err(nom::Err::Error(error)) => {
+                if furthest
+                    .as_ref()
+                    .is_none_or(|f| offset(&error) >= offset(f))
+                {
+                    furthest = Some(error);
+                }
+            }
+            result => return result,
+        }
+    }
+    let error = furthest.unwrap_or_else(|| VerboseError::from_error_kind(input, ErrorKind::Alt));
+    Err(nom::Err::Error(error))
+}
+
+/// A combinator that gives the ExprKind from `inner` the Span of the source
+/// it read, leaving out whitespace on either side.
+pub(crate) fn spanned<'a, F>(
+    mut inner: F,
+) -> impl FnMut(Input<'a>) -> IResult<Input<'a>, Expr, VerboseError<Input<'a>>>
 where
-    F: FnMut(&'a str) -> IResult<&'a str, O, E>,
+    F: FnMut(Input<'a>) -> IResult<Input<'a>, ExprKind, VerboseError<Input<'a>>>,
 {
-    delimited(multispace0, comment(inner), multispace0)
+    move |input| {
+        let (i, start) = start(input)?;
+        let (rest, kind) = inner(i)?;
+        let end = start.advance(consumed(i, rest).trim_end());
+        Ok((rest, Expr::new(kind, Span::new(start, end))))
+    }
 }
 
-fn comment<'a, F: 'a, O, E: ParseError<&'a str>>(
+/// A Binary Expr spanning from `lhs` to `rhs`.
+pub(crate) fn binary(op: Operator, lhs: Expr, rhs: Expr) -> Expr {
+    let span = lhs.span.to(rhs.span);
+    let (lhs, rhs) = (Box::new(lhs), Box::new(rhs));
+    Expr::new(ExprKind::Binary { op, lhs, rhs }, span)
+}
+
+/// A combinator that takes a parser `inner` and produces a parser that also consumes both leading and
+/// trailing whitespace, and language comments returning the output of `inner`.
+pub(crate) fn trim<'a, F, O>(
     inner: F,
-) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+) -> impl FnMut(Input<'a>) -> IResult<Input<'a>, O, VerboseError<Input<'a>>>
 where
-    F: FnMut(&'a str) -> IResult<&'a str, O, E>,
+    F: FnMut(Input<'a>) -> IResult<Input<'a>, O, VerboseError<Input<'a>>>,
 {
-    delimited(
-        many0(tuple((tag("//"), take_until("\n"), tag("\n")))),
-        inner,
-        many0(tuple((tag("//"), take_until("\n"), tag("\n")))),
-    )
+    delimited(skip, inner, skip)
+}
+
+fn skip(input: Input) -> IResult<Input, (), VerboseError<Input>> {
+    map(many0(alt((multispace1, comment))), |_| ())(input)
+}
+
+// COMMENT → "//" ... "\n" | "/*" ... "*/" ;
+fn comment(input: Input) -> IResult<Input, Input, VerboseError<Input>> {
+    alt((line_comment, block_comment))(input)
+}
+
+/// A "//" comment up to the end of the line or the input.
+fn line_comment(input: Input) -> IResult<Input, Input, VerboseError<Input>> {
+    delimited(tag("//"), not_line_ending, opt(line_ending))(input)
 }
 
-fn _test_trim(i: &str) -> IResult<&str, &str> {
+/// A "/* */" comment, "/*" inside it starts another that has to be closed too.
+pub(crate) fn block_comment(input: Input) -> IResult<Input, Input, VerboseError<Input>> {
+    let (mut i, _) = tag("/*")(input)?;
+    let mut depth = 1;
+    while depth > 0 {
+        let text = i.fragment();
+        let (next, change) = match (text.find("/*"), text.find("*/")) {
+            (Some(open), Some(close)) if open < close => (open, 1),
+            (_, Some(close)) => (close, -1),
+            (_, None) => {
+                let context = VerboseErrorKind::Context("unterminated block comment");
+                return Err(nom::Err::Failure(VerboseError {
+                    errors: vec![(input, context)],
+                }));
+            }
+        };
+        depth += change;
+        i = i.take_split(next + 2).0;
+    }
+    let length = i.location_offset() - input.location_offset();
+    Ok((i, input.take_split(length).1))
+}
+
+/// Runs `parser` on `input` returning what is left as a `&str`.
+#[cfg(test)]
+pub(crate) fn parse<'a, O>(
+    mut parser: impl FnMut(Input<'a>) -> IResult<Input<'a>, O, VerboseError<Input<'a>>>,
+    input: &'a str,
+) -> Result<(&'a str, O), nom::Err<VerboseError<Input<'a>>>> {
+    parser(Input::new(input)).map(|(rest, output)| (*rest.fragment(), output))
+}
+
+fn _test_trim(i: Input) -> IResult<Input, Input, VerboseError<Input>> {
     trim(tag("tag"))(i)
 }
 
 #[test]
 fn trim_parser() {
     let data = "    // Hey there \ntag";
-    assert_eq!(_test_trim(data), Ok(("", "tag")));
+    assert_eq!(
+        parse(_test_trim, data).map(|(rest, tag)| (rest, *tag.fragment())),
+        Ok(("", "tag"))
+    );
+}
+
+#[test]
+fn spanned_skips_whitespace() {
+    use super::Constant;
+    let kind = |i| map(tag("abc"), |_| ExprKind::Constant(Constant::Null))(i);
+    let (rest, expr) = parse(spanned(trim(kind)), "// note\n  abc  \n").unwrap();
+    assert_eq!(rest, "");
+    assert_eq!(
+        expr.span,
+        Span::new(Position::new(2, 3, 10), Position::new(2, 6, 13))
+    );
+}
+
+#[test]
+fn comments() {
+    let (rest, _) = parse(start, "/* one /* two */ still one */ x").unwrap();
+    assert_eq!(rest, "x");
+    let (rest, _) = parse(start, "  // last line").unwrap();
+    assert_eq!(rest, "");
+    assert!(matches!(
+        parse(start, "/* one /* two */ x"),
+        Err(nom::Err::Failure(_))
+    ));
+    let (rest, doc) = parse(doc_comments, "// not this\n/// Adds.\n///\n/// Twice.\nadd").unwrap();
+    assert_eq!(rest, "add");
+    assert_eq!(doc.as_deref(), Some("Adds.\n\nTwice."));
 }
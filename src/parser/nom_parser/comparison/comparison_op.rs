@@ -1,4 +1,7 @@
-use super::{common::trim, Operator};
+use super::{
+    common::{trim, Input},
+    Operator,
+};
 use nom::{
     bytes::complete::tag,
     character::complete::one_of,
@@ -7,13 +10,13 @@ use nom::{
 };
 
 // Parser looks for a >, >=, <, <=.
-pub(crate) fn parser(input: &str) -> IResult<&str, Operator, VerboseError<&str>> {
+pub(crate) fn parser(input: Input) -> IResult<Input, Operator, VerboseError<Input>> {
     let (i, front) = context(
         "Not a Comparison Operator of `>`, `>=`, `<`, `<=`.",
         trim(one_of("><")),
     )(input)?;
 
-    fn equals_parse(i: &str) -> IResult<&str, &str> {
+    fn equals_parse(i: Input) -> IResult<Input, Input, VerboseError<Input>> {
         trim(tag("="))(i)
     }
 
@@ -39,8 +42,9 @@ pub(crate) fn parser(input: &str) -> IResult<&str, Operator, VerboseError<&str>>
 
 #[test]
 fn combinator_op_parser() {
-    assert_eq!(parser(" > "), Ok(("", Operator::GreaterThen,)));
-    assert_eq!(parser(" < "), Ok(("", Operator::LessThen,)));
-    assert_eq!(parser(" >= "), Ok(("", Operator::GreaterThenEqual,)));
-    assert_eq!(parser(" <= "), Ok(("", Operator::LessThenEqual,)));
+    use super::common::parse;
+    assert_eq!(parse(parser, " > "), Ok(("", Operator::GreaterThen,)));
+    assert_eq!(parse(parser, " < "), Ok(("", Operator::LessThen,)));
+    assert_eq!(parse(parser, " >= "), Ok(("", Operator::GreaterThenEqual,)));
+    assert_eq!(parse(parser, " <= "), Ok(("", Operator::LessThenEqual,)));
 }
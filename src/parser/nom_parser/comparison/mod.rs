@@ -1,4 +1,8 @@
-use super::{common, term, Expr, Operator};
+use super::{
+    common,
+    common::{binary, Input},
+    term, Expr, Operator,
+};
 
 use nom::{
     branch::alt,
@@ -11,82 +15,79 @@ use nom::{
 mod comparison_op;
 
 // comparison     → term ( ( ">" | ">=" | "<" | "<=" ) term )* ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Comparison");
     }
-    context("Term Parser", alt((comparison_parser, term::parser)))(input)
+    context("Comparison Parser", alt((comparison_parser, term::parser)))(input)
 }
 
 #[test]
 fn comparison_parse() {
-    use super::Constant;
+    use super::common::parse;
+    use super::{Constant, ExprKind};
     let data = " 1 > 2 ";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Binary {
+            Expr::from(ExprKind::Binary {
                 op: Operator::GreaterThen,
                 lhs: Box::new(Constant::Int(1).into()),
                 rhs: Box::new(Constant::Int(2).into()),
-            }
+            })
         ))
     );
 
     let data = "// lkjdsfaklsfjkjasdflkjdafs \n!true";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Unary {
+            Expr::from(ExprKind::Unary {
                 op: Operator::Bang,
                 child: Box::new(Constant::Boolean(true).into())
-            }
+            })
         ))
     );
 }
 
 #[test]
 fn comparison_parser_mult_depth() {
-    use super::Constant;
+    use super::common::parse;
+    use super::{Constant, ExprKind};
     let data = "1 + 1 + 1 + 1";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Binary {
+            Expr::from(ExprKind::Binary {
                 op: Operator::Plus,
-                lhs: Box::new(Expr::Binary {
+                lhs: Box::new(Expr::from(ExprKind::Binary {
                     op: Operator::Plus,
-                    lhs: Box::new(Expr::Binary {
+                    lhs: Box::new(Expr::from(ExprKind::Binary {
                         op: Operator::Plus,
-                        lhs: Box::new(Expr::Constant(Constant::Int(1))),
-                        rhs: Box::new(Expr::Constant(Constant::Int(1)))
-                    }),
-                    rhs: Box::new(Expr::Constant(Constant::Int(1)))
-                }),
-                rhs: Box::new(Expr::Constant(Constant::Int(1)))
-            }
+                        lhs: Box::new(Expr::from(Constant::Int(1))),
+                        rhs: Box::new(Expr::from(Constant::Int(1)))
+                    })),
+                    rhs: Box::new(Expr::from(Constant::Int(1)))
+                })),
+                rhs: Box::new(Expr::from(Constant::Int(1)))
+            })
         ))
     );
 }
 
-pub fn comparison_parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn comparison_parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     let (i, lhs) = term::parser(input)?;
     let (i, op) = comparison_op::parser(i)?;
     let (i, rhs) = term::parser(i)?;
-    let lhs = Box::new(lhs);
-    let rhs = Box::new(rhs);
-    let expr = Expr::Binary { op, lhs, rhs };
+    let expr = binary(op, lhs, rhs);
     match many1(pair(comparison_op::parser, term::parser))(i) {
         Ok((i, vec)) => Ok((
             i,
-            vec.iter().fold(expr, |lhs, (op, rhs)| Expr::Binary {
-                op: *op,
-                lhs: Box::new(lhs),
-                rhs: Box::new(rhs.clone()),
-            }),
+            vec.into_iter()
+                .fold(expr, |lhs, (op, rhs)| binary(op, lhs, rhs)),
         )),
         Err(_) => Ok((i, expr)),
     }
@@ -1,39 +1,79 @@
 /*
  * conditional/mod.rs
  */
-use super::{common::trim, expression, statement, Expr};
+use super::{
+    common::{furthest, start, trim, Input},
+    expression, statement, Expr, ExprKind, Span,
+};
 
-use nom::{branch::alt, bytes::complete::tag, error::VerboseError, IResult};
+use nom::{
+    bytes::complete::tag,
+    error::{VerboseError, VerboseErrorKind},
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
 
 // ifStmt → "if" expression "then" statement ( "else" statement )? ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Conditional");
     }
-    alt((if_else_stmt, if_stmt, expression::parser))(input)
+    furthest(
+        input,
+        &mut [&mut if_else_stmt, &mut if_stmt, &mut expression::parser],
+    )
 }
 
-fn if_stmt(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
-    let (i, _) = trim(tag("if"))(input)?;
+fn if_stmt(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
+    let (i, start) = start(input)?;
+    let (i, _) = trim(tag("if"))(i)?;
     let (i, expr) = expression::parser(i)?;
     let (i, _) = trim(tag("then"))(i)?;
-    let (i, statement) = statement::parser(i)?;
-    Ok((i, Expr::If(Box::new(expr), Box::new(statement))))
+    let (i, statement) = statement::single(i)?;
+    let span = Span::new(start, statement.span.end);
+    Ok((
+        i,
+        Expr::new(ExprKind::If(Box::new(expr), Box::new(statement)), span),
+    ))
 }
 
-fn if_else_stmt(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
-    let (i, _) = trim(tag("if"))(input)?;
+fn if_else_stmt(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
+    let (i, start) = start(input)?;
+    let (i, _) = trim(tag("if"))(i)?;
     let (i, expr) = expression::parser(i)?;
     let (i, _) = trim(tag("then"))(i)?;
-    let (i, statement) = statement::parser(i)?;
+    let (i, statement) = statement::single(i)?;
+    sequence_in_then(i)?;
     let (i, _) = trim(tag("else"))(i)?;
-    let (i, else_statement) = statement::parser(i)?;
+    let (i, else_statement) = statement::single(i)?;
+    let span = Span::new(start, else_statement.span.end);
     Ok((
         i,
-        Expr::IfElse(
-            Box::new(expr),
-            Box::new(statement),
-            Box::new(else_statement),
+        Expr::new(
+            ExprKind::IfElse(
+                Box::new(expr),
+                Box::new(statement),
+                Box::new(else_statement),
+            ),
+            span,
         ),
     ))
 }
+
+/// `if c then a; b else d` would otherwise be read as `(if c then a); b`
+/// with `else d` left over, so it is an Error pointing at the ";".
+fn sequence_in_then(input: Input) -> Result<(), nom::Err<VerboseError<Input>>> {
+    let rest = separated_list1(trim(tag(";")), statement::single);
+    match tuple((trim(tag(";")), rest, trim(tag("else"))))(input) {
+        Ok((_, (semicolon, _, _))) => {
+            let context = VerboseErrorKind::Context(
+                "a `;` sequence needs parentheses inside `then`, `then (a; b) else c`",
+            );
+            Err(nom::Err::Failure(VerboseError {
+                errors: vec![(semicolon, context)],
+            }))
+        }
+        Err(_) => Ok(()),
+    }
+}
@@ -3,21 +3,20 @@
  *
  * Handles all Declarations in Grammer.
  */
+use super::common::{furthest, Input};
 use super::{function, statement, Expr};
 
 use nom::{
-    branch::alt,
     error::{context, VerboseError},
     IResult,
 };
 
 // ❌   declaration  → funDecl | statement ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Declaration");
     }
-    context(
-        "Declarations Parser",
-        alt((function::parser, statement::parser)),
-    )(input)
+    context("Declarations Parser", |i| {
+        furthest(i, &mut [&mut function::parser, &mut statement::parser])
+    })(input)
 }
@@ -5,6 +5,7 @@
  */
 
 // use crate::parser::common::trim;
+use super::super::common::Input;
 use super::Operator;
 use nom::{
     bytes::complete::tag,
@@ -16,14 +17,14 @@ use nom::{
 };
 
 // Parser looks for a ==, !=.
-pub(crate) fn parser(input: &str) -> IResult<&str, Operator, VerboseError<&str>> {
+pub(crate) fn parser(input: Input) -> IResult<Input, Operator, VerboseError<Input>> {
     let (i, token) = context(
         "Not a Equality Operator of `==`, `!=`.",
         recognize(pair(one_of("!="), tag("="))),
     )(input)?;
     Ok((
         i,
-        match token {
+        match *token.fragment() {
             "==" => Operator::Equality,
             "!=" => Operator::NotEqual,
             _ => unreachable!(),
@@ -33,6 +34,7 @@ pub(crate) fn parser(input: &str) -> IResult<&str, Operator, VerboseError<&str>>
 
 #[test]
 fn equality_op_parse() {
-    assert_eq!(parser("=="), Ok(("", Operator::Equality,)));
-    assert_eq!(parser("!="), Ok(("", Operator::NotEqual,)));
+    use super::super::common::parse;
+    assert_eq!(parse(parser, "=="), Ok(("", Operator::Equality,)));
+    assert_eq!(parse(parser, "!="), Ok(("", Operator::NotEqual,)));
 }
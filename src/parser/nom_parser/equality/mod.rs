@@ -3,7 +3,10 @@
  *
  * This file Handles the Equality Grammer
  */
-use super::{common::trim, comparison, Expr, Operator};
+use super::{
+    common::{binary, trim, Input},
+    comparison, Expr, Operator,
+};
 
 use nom::{
     branch::alt,
@@ -16,7 +19,7 @@ use nom::{
 mod equality_op;
 
 // equality       → comparison ( ( "!=" | "==" ) comparison )* ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Equality");
     }
@@ -28,73 +31,70 @@ pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
 
 #[test]
 fn equality_parse() {
-    use super::Constant;
+    use super::common::parse;
+    use super::{Constant, ExprKind};
     let data = " 1 == 2 ";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Binary {
+            Expr::from(ExprKind::Binary {
                 op: Operator::Equality,
                 lhs: Box::new(Constant::Int(1).into()),
                 rhs: Box::new(Constant::Int(2).into()),
-            }
+            })
         ))
     );
 
     let data = "// lkjdsfaklsfjkjasdflkjdafs \n!true";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Unary {
+            Expr::from(ExprKind::Unary {
                 op: Operator::Bang,
                 child: Box::new(Constant::Boolean(true).into())
-            }
+            })
         ))
     );
 }
 
 #[test]
 fn equality_parser_mult_depth() {
-    use super::Constant;
+    use super::common::parse;
+    use super::{Constant, ExprKind};
     let data = "1 + 1 + 1 + 1";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Binary {
+            Expr::from(ExprKind::Binary {
                 op: Operator::Plus,
-                lhs: Box::new(Expr::Binary {
+                lhs: Box::new(Expr::from(ExprKind::Binary {
                     op: Operator::Plus,
-                    lhs: Box::new(Expr::Binary {
+                    lhs: Box::new(Expr::from(ExprKind::Binary {
                         op: Operator::Plus,
-                        lhs: Box::new(Expr::Constant(Constant::Int(1))),
-                        rhs: Box::new(Expr::Constant(Constant::Int(1)))
-                    }),
-                    rhs: Box::new(Expr::Constant(Constant::Int(1)))
-                }),
-                rhs: Box::new(Expr::Constant(Constant::Int(1)))
-            }
+                        lhs: Box::new(Expr::from(Constant::Int(1))),
+                        rhs: Box::new(Expr::from(Constant::Int(1)))
+                    })),
+                    rhs: Box::new(Expr::from(Constant::Int(1)))
+                })),
+                rhs: Box::new(Expr::from(Constant::Int(1)))
+            })
         ))
     );
 }
 
-pub fn equality_parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn equality_parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     let (i, lhs) = comparison::parser(input)?;
     let (i, op) = equality_op::parser(i)?;
     let (i, rhs) = comparison::parser(i)?;
-    let lhs = Box::new(lhs);
-    let rhs = Box::new(rhs);
-    let expr = Expr::Binary { op, lhs, rhs };
+    let expr = binary(op, lhs, rhs);
     match many1(pair(equality_op::parser, comparison::parser))(i) {
         Ok((i, vec)) => Ok((
             i,
-            vec.iter().fold(expr, |lhs, (op, rhs)| Expr::Binary {
-                op: *op,
-                lhs: Box::new(lhs),
-                rhs: Box::new(rhs.clone()),
-            }),
+            vec.into_iter()
+                .fold(expr, |lhs, (op, rhs)| binary(op, lhs, rhs)),
         )),
         Err(_) => Ok((i, expr)),
     }
@@ -1,12 +1,13 @@
 /*
  * expression/mod.rs
  */
+use super::common::Input;
 use super::{logic, Expr};
 
 use nom::{error::VerboseError, IResult};
 
 // ❌✅ expression   → logic_or ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Equality");
     }
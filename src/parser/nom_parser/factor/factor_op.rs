@@ -1,4 +1,7 @@
-use super::{common::trim, Operator};
+use super::{
+    common::{trim, Input},
+    Operator,
+};
 use nom::{
     character::complete::one_of,
     combinator::map,
@@ -7,7 +10,7 @@ use nom::{
 };
 
 // Parser looks for a * or /.
-pub(crate) fn parser(input: &str) -> IResult<&str, Operator, VerboseError<&str>> {
+pub(crate) fn parser(input: Input) -> IResult<Input, Operator, VerboseError<Input>> {
     context(
         "Not a * or / Operator",
         map(trim(one_of("*/")), |c: char| match c {
@@ -20,6 +23,7 @@ pub(crate) fn parser(input: &str) -> IResult<&str, Operator, VerboseError<&str>>
 
 #[test]
 fn factor_op_parser() {
-    assert_eq!(parser(" * "), Ok(("", Operator::Multiply,)));
-    assert_eq!(parser(" / "), Ok(("", Operator::Divide,)));
+    use super::common::parse;
+    assert_eq!(parse(parser, " * "), Ok(("", Operator::Multiply,)));
+    assert_eq!(parse(parser, " / "), Ok(("", Operator::Divide,)));
 }
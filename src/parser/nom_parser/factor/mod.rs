@@ -1,4 +1,8 @@
-use super::{common, unary, Expr, Operator};
+use super::{
+    common,
+    common::{binary, Input},
+    unary, Expr, Operator,
+};
 
 use nom::{
     branch::alt,
@@ -11,7 +15,7 @@ use nom::{
 mod factor_op;
 
 // factor         → unary ( ( "/" | "*" ) unary )* ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Factor");
     }
@@ -20,73 +24,70 @@ pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
 
 #[test]
 fn factor_parse() {
-    use super::node::{Constant, Operator};
+    use super::common::parse;
+    use super::{Constant, ExprKind};
     let data = " 1 * 2 ";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Binary {
+            Expr::from(ExprKind::Binary {
                 op: Operator::Multiply,
                 lhs: Box::new(Constant::Int(1).into()),
                 rhs: Box::new(Constant::Int(2).into()),
-            }
+            })
         ))
     );
 
     let data = "// lkjdsfaklsfjkjasdflkjdafs \n!true";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Unary {
+            Expr::from(ExprKind::Unary {
                 op: Operator::Bang,
                 child: Box::new(Constant::Boolean(true).into())
-            }
+            })
         ))
     );
 }
 
 #[test]
 fn factor_parser_mult_depth() {
-    use super::node::{Constant, Operator};
+    use super::common::parse;
+    use super::{Constant, ExprKind};
     let data = "5 * 2 * 2 / 2";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Binary {
+            Expr::from(ExprKind::Binary {
                 op: Operator::Divide,
-                lhs: Box::new(Expr::Binary {
+                lhs: Box::new(Expr::from(ExprKind::Binary {
                     op: Operator::Multiply,
-                    lhs: Box::new(Expr::Binary {
+                    lhs: Box::new(Expr::from(ExprKind::Binary {
                         op: Operator::Multiply,
-                        lhs: Box::new(Expr::Constant(Constant::Int(5))),
-                        rhs: Box::new(Expr::Constant(Constant::Int(2)))
-                    }),
-                    rhs: Box::new(Expr::Constant(Constant::Int(2)))
-                }),
-                rhs: Box::new(Expr::Constant(Constant::Int(2)))
-            }
+                        lhs: Box::new(Expr::from(Constant::Int(5))),
+                        rhs: Box::new(Expr::from(Constant::Int(2)))
+                    })),
+                    rhs: Box::new(Expr::from(Constant::Int(2)))
+                })),
+                rhs: Box::new(Expr::from(Constant::Int(2)))
+            })
         ))
     );
 }
 
-pub fn factor_parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn factor_parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     let (i, lhs) = unary::parser(input)?;
     let (i, op) = factor_op::parser(i)?;
     let (i, rhs) = unary::parser(i)?;
-    let lhs = Box::new(lhs);
-    let rhs = Box::new(rhs);
-    let expr = Expr::Binary { op, lhs, rhs };
+    let expr = binary(op, lhs, rhs);
     match many1(pair(factor_op::parser, unary::parser))(i) {
         Ok((i, vec)) => Ok((
             i,
-            vec.iter().fold(expr, |lhs, (op, rhs)| Expr::Binary {
-                op: *op,
-                lhs: Box::new(lhs),
-                rhs: Box::new(rhs.clone()),
-            }),
+            vec.into_iter()
+                .fold(expr, |lhs, (op, rhs)| binary(op, lhs, rhs)),
         )),
         Err(_) => Ok((i, expr)),
     }
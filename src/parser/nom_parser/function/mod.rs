@@ -3,102 +3,213 @@
  *
  * Handles all Declarations in Grammer.
  */
-use super::{common::trim, parameters, statement, Expr};
+use super::{
+    common::{block_comment, start, trim, Input},
+    parameters, statement, Expr, ExprKind, Span,
+};
 
 use nom::{
     bytes::complete::tag,
-    bytes::complete::take_till,
-    combinator::map,
-    error::{context, VerboseError},
-    IResult,
+    combinator::{cut, map, not},
+    error::{context, VerboseError, VerboseErrorKind},
+    sequence::terminated,
+    IResult, InputTake,
 };
 
 // ❌   function      → IDENTIFIER parameter? "=" statement "\n" ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+//
+// The body carries on over lines indented deeper than the IDENTIFIER.
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking Function");
     }
-    let (i, func) = trim(take_till(|c| c == '\n'))(input)?;
-    Ok((i, function(func)?.1))
+    let (i, begin) = start(input)?;
+    let (rest, body) = layout(i, begin.column);
+    let (left, func) = function(body)?;
+    // Once there is an "=" this is a Function so anything after the body is an Error.
+    let (left, _) = start(left)?;
+    if !left.fragment().is_empty() {
+        let context = VerboseErrorKind::Context("expected the end of the function");
+        return Err(nom::Err::Failure(VerboseError {
+            errors: vec![(left, context)],
+        }));
+    }
+    Ok((rest, func))
+}
+
+/// Splits `input` after its first line and every line after that is
+/// indented past `column`, blank lines and comments included when more of
+/// the body follows them.  A block comment is skipped whole, so its later
+/// lines can start in any column.
+fn layout(input: Input, column: usize) -> (Input, Input) {
+    let text = input.fragment();
+    let line_end = |from: usize| text[from..].find('\n').map_or(text.len(), |n| from + n + 1);
+    let mut end = line_end(0);
+    let mut next = end;
+    while next < text.len() {
+        let stop = line_end(next);
+        let line = &text[next..stop];
+        let code = line.trim_start();
+        let indented = line[..line.len() - code.len()].chars().count() >= column;
+        if code.starts_with("/*") {
+            let comment = input.take_split(next + line.len() - code.len()).0;
+            let after = match block_comment(comment) {
+                Ok((after, _)) => after.location_offset() - input.location_offset(),
+                // Left in the body so the function reports it.
+                Err(_) if indented => return input.take_split(text.len()),
+                Err(_) => break,
+            };
+            next = line_end(after);
+            if text[after..next].trim().is_empty() {
+                continue;
+            }
+            // Code after the comment on its last line.
+            if !indented {
+                break;
+            }
+            end = next;
+            continue;
+        }
+        next = stop;
+        if code.is_empty() || code.starts_with("//") {
+            continue;
+        }
+        if !indented {
+            break;
+        }
+        end = stop;
+    }
+    input.take_split(end)
 }
 
 // ❌   function      → IDENTIFIER parameter? "=" statement "\n" ;
-pub fn function(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn function(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     // TODO: Make these primary calls
-    let (i, ident) = parameters::identifier(input)?;
+    let (i, start) = start(input)?;
+    let (i, ident) = parameters::identifier(i)?;
     let (i, param) = parameters::parser(i)?;
     let (i, _) = equals(i)?;
-    let (i, stmt) = statement::parser(i)?;
-    Ok((i, Expr::Function(ident, param, Box::new(stmt))))
+    // After the "=" this can only be a Function, so report the body's Error.
+    let (i, stmt) = cut(statement::parser)(i)?;
+    let span = Span::new(start, stmt.span.end);
+    Ok((
+        i,
+        Expr::new(ExprKind::Function(ident, param, Box::new(stmt), None), span),
+    ))
 }
 
-pub fn equals(input: &str) -> IResult<&str, (), VerboseError<&str>> {
-    context("Function Equals", map(trim(tag("=")), |_| ()))(input)
+pub fn equals(input: Input) -> IResult<Input, (), VerboseError<Input>> {
+    context(
+        "Function Equals",
+        map(trim(terminated(tag("="), not(tag("=")))), |_| ()),
+    )(input)
 }
 
 #[test]
 fn funciton_line_test() {
+    use super::common::parse;
     use super::{Constant, Expr, Operator};
     let data = "add x y = x + y\n1";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "1",
-            Expr::Function(
+            Expr::from(ExprKind::Function(
                 "add".into(),
                 vec!["x".to_string(), "y".to_string()],
-                Box::new(Expr::Binary {
+                Box::new(Expr::from(ExprKind::Binary {
                     op: Operator::Plus,
-                    lhs: Box::new(Expr::Call(
-                        Box::new(Expr::Constant(Constant::Keyword("x".into()))),
+                    lhs: Box::new(Expr::from(ExprKind::Call(
+                        Box::new(Expr::from(Constant::Keyword("x".into()))),
                         vec![]
-                    )),
-                    rhs: Box::new(Expr::Call(
-                        Box::new(Expr::Constant(Constant::Keyword("y".into()))),
+                    ))),
+                    rhs: Box::new(Expr::from(ExprKind::Call(
+                        Box::new(Expr::from(Constant::Keyword("y".into()))),
                         vec![]
-                    ))
-                })
-            )
+                    )))
+                })),
+                None
+            ))
         ))
     );
 }
 
 #[test]
 fn funciton_no_args() {
+    use super::common::parse;
     use super::{Constant, Expr};
     let data = "name = \"Cowboy\"\n1";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "1",
-            Expr::Function(
+            Expr::from(ExprKind::Function(
                 "name".into(),
                 Vec::new(),
-                Box::new(Constant::String("Cowboy".into()).into())
-            )
+                Box::new(Constant::String("Cowboy".into()).into()),
+                None
+            ))
         ))
     );
 }
 
 #[test]
 fn funciton_after_function() {
+    use super::common::parse;
     use super::{Constant, Expr};
 
     let data = "name = \"Cowboy\"\nnum = 1\n";
-    let (i, left) = parser(data).unwrap();
+    let (i, left) = parse(parser, data).unwrap();
     assert_eq!(
         left,
-        Expr::Function(
+        Expr::from(ExprKind::Function(
             "name".into(),
             Vec::new(),
-            Box::new(Constant::String("Cowboy".into()).into())
-        )
+            Box::new(Constant::String("Cowboy".into()).into()),
+            None
+        ))
     );
     assert_eq!(i, "num = 1\n");
-    let (i, left) = parser(i).unwrap();
+    let (i, left) = parse(parser, i).unwrap();
     assert_eq!(
         left,
-        Expr::Function("num".into(), Vec::new(), Box::new(Constant::Int(1).into()))
+        Expr::from(ExprKind::Function(
+            "num".into(),
+            Vec::new(),
+            Box::new(Constant::Int(1).into()),
+            None
+        ))
     );
     assert_eq!(i, "");
 }
+
+#[test]
+fn funciton_body_over_lines() {
+    use super::common::parse;
+    let data = "sign n =\n    if n > 0 then\n        1\n\n    else\n        0\nsign 2\n";
+    let (i, func) = parse(parser, data).unwrap();
+    assert_eq!(i, "sign 2\n");
+    match func.kind {
+        ExprKind::Function(name, _, body, _) => {
+            assert_eq!(name, "sign");
+            assert!(matches!(body.kind, ExprKind::IfElse(..)));
+            assert_eq!(body.span.end.line, 6);
+        }
+        kind => panic!("expected Function found {:?}", kind),
+    }
+}
+
+#[test]
+fn funciton_body_over_block_comment() {
+    use super::common::parse;
+    let data = "f x =\n    /* a\ncomment */\n    x + 1\nf 2\n";
+    let (i, func) = parse(parser, data).unwrap();
+    assert_eq!(i, "f 2\n");
+    match func.kind {
+        ExprKind::Function(_, _, body, _) => {
+            assert!(matches!(body.kind, ExprKind::Binary { .. }));
+            assert_eq!(body.span.start.line, 4);
+        }
+        kind => panic!("expected Function found {:?}", kind),
+    }
+}
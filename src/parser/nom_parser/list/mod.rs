@@ -0,0 +1,63 @@
+/*
+ * list/mod.rs
+ *
+ * Handles List literal Grammer
+ */
+use super::{
+    common::{spanned, trim, Input},
+    expression, Expr, ExprKind,
+};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::map,
+    error::{context, VerboseError},
+    multi::separated_list0,
+    sequence::delimited,
+    IResult,
+};
+
+// list → "[" ( expression ( "," expression )* )? "]" ;
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
+    if cfg!(feature = "announce") {
+        println!("Checking for List");
+    }
+    context(
+        "List Parser",
+        spanned(map(
+            delimited(
+                trim(tag("[")),
+                separated_list0(trim(tag(",")), expression::parser),
+                trim(char(']')),
+            ),
+            ExprKind::List,
+        )),
+    )(input)
+}
+
+#[test]
+fn list_parse() {
+    use super::common::parse;
+    use super::{Constant, Operator};
+    let data = "[1, 2 + 3, \"four\"]";
+    assert_eq!(
+        parse(parser, data),
+        Ok((
+            "",
+            Expr::from(ExprKind::List(vec![
+                Constant::Int(1).into(),
+                Expr::from(ExprKind::Binary {
+                    op: Operator::Plus,
+                    lhs: Box::new(Constant::Int(2).into()),
+                    rhs: Box::new(Constant::Int(3).into()),
+                }),
+                Constant::String("four".into()).into(),
+            ]))
+        ))
+    );
+    assert_eq!(
+        parse(parser, "[ ]"),
+        Ok(("", Expr::from(ExprKind::List(vec![]))))
+    );
+}
@@ -3,7 +3,7 @@
  *
  * Handles all 'and' in Grammer.
  */
-use super::{common::trim, equality, Expr};
+use super::{common::trim, common::Input, equality, Expr, ExprKind};
 
 use nom::{
     bytes::complete::tag,
@@ -15,7 +15,7 @@ use nom::{
 };
 
 // ✅ logic_and    → equality ( "and" equality )* ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for AND");
     }
@@ -23,14 +23,9 @@ pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
     match and_word_parser(i) {
         Ok((i, _)) => {
             let (i, rhs) = equality::parser(i)?;
-            let expr = Expr::And(Box::new(lhs), Box::new(rhs));
+            let expr = and(lhs, rhs);
             match many1(preceded(tag("and"), equality::parser))(i) {
-                Ok((i, vec)) => Ok((
-                    i,
-                    vec.iter().fold(expr, |lhs, rhs| {
-                        Expr::And(Box::new(lhs), Box::new(rhs.clone()))
-                    }),
-                )),
+                Ok((i, vec)) => Ok((i, vec.into_iter().fold(expr, and))),
                 Err(_) => Ok((i, expr)),
             }
         }
@@ -38,6 +33,11 @@ pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
     }
 }
 
-fn and_word_parser(input: &str) -> IResult<&str, (), VerboseError<&str>> {
+fn and_word_parser(input: Input) -> IResult<Input, (), VerboseError<Input>> {
     context("not and keyword", map(trim(tag("and")), |_| ()))(input)
 }
+
+fn and(lhs: Expr, rhs: Expr) -> Expr {
+    let span = lhs.span.to(rhs.span);
+    Expr::new(ExprKind::And(Box::new(lhs), Box::new(rhs)), span)
+}
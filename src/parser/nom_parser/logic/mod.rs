@@ -4,6 +4,6 @@
  * Handles all Logic in Grammer.
  */
 
-use super::{common, equality, Expr};
+use super::{common, equality, Expr, ExprKind};
 mod and;
 pub mod or;
@@ -3,7 +3,7 @@
  *
  * Handles all 'or' in Grammer.
  */
-use super::{and, common::trim, Expr};
+use super::{and, common::trim, common::Input, Expr, ExprKind};
 
 use nom::{
     bytes::complete::tag,
@@ -15,7 +15,7 @@ use nom::{
 };
 
 //✅logic_or     → logic_and ( "or" logic_and )* ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for OR");
     }
@@ -23,14 +23,9 @@ pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
     match and_word_parser(i) {
         Ok((i, _)) => {
             let (i, rhs) = and::parser(i)?;
-            let expr = Expr::Or(Box::new(lhs), Box::new(rhs));
+            let expr = or(lhs, rhs);
             match many1(preceded(and_word_parser, and::parser))(i) {
-                Ok((i, vec)) => Ok((
-                    i,
-                    vec.iter().fold(expr, |lhs, rhs| {
-                        Expr::Or(Box::new(lhs), Box::new(rhs.clone()))
-                    }),
-                )),
+                Ok((i, vec)) => Ok((i, vec.into_iter().fold(expr, or))),
                 Err(_) => Ok((i, expr)),
             }
         }
@@ -38,6 +33,11 @@ pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
     }
 }
 
-fn and_word_parser(input: &str) -> IResult<&str, (), VerboseError<&str>> {
+fn and_word_parser(input: Input) -> IResult<Input, (), VerboseError<Input>> {
     context("or and keyword", map(trim(tag("or")), |_| ()))(input)
 }
+
+fn or(lhs: Expr, rhs: Expr) -> Expr {
+    let span = lhs.span.to(rhs.span);
+    Expr::new(ExprKind::Or(Box::new(lhs), Box::new(rhs)), span)
+}
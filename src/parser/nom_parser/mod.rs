@@ -11,17 +11,55 @@ mod equality;
 mod expression;
 mod factor;
 mod function;
+mod list;
 mod logic;
 mod parameters;
 mod primary;
-mod print;
 mod program;
 mod statement;
 mod term;
 mod unary;
 
-pub use crate::parser::{Builtin, Constant, Expr, Operator};
+pub use crate::parser::{Constant, Expr, ExprKind, Operator, Position, Span};
+pub(crate) use common::KEYWORDS;
 
+/// Parses a whole program, input the parser stops before is an Error rather
+/// than being left over.
 pub fn parser(input: &str) -> IResult<&str, Vec<Expr>, VerboseError<&str>> {
-    common::trim(program::parser)(input)
+    program::parser(common::Input::new(input))
+        .map(|(rest, exprs)| (*rest.fragment(), exprs))
+        .map_err(|e| {
+            e.map(|e| VerboseError {
+                errors: e
+                    .errors
+                    .into_iter()
+                    .map(|(input, kind)| (*input.fragment(), kind))
+                    .collect(),
+            })
+        })
+}
+
+#[test]
+fn parser_spans() {
+    let (_, exprs) = parser("one = 1\n\nadd x y = x + y\n").unwrap();
+    let span = |line, column, offset| Position::new(line, column, offset);
+    assert_eq!(exprs[0].span, Span::new(span(1, 1, 0), span(1, 8, 7)));
+    match &exprs[1].kind {
+        ExprKind::Function(_, _, body, _) => match &body.kind {
+            ExprKind::Binary { lhs, rhs, .. } => {
+                assert_eq!(body.span, Span::new(span(3, 11, 19), span(3, 16, 24)));
+                assert_eq!(lhs.span, Span::new(span(3, 11, 19), span(3, 12, 20)));
+                assert_eq!(rhs.span, Span::new(span(3, 15, 23), span(3, 16, 24)));
+            }
+            kind => panic!("expected Binary found {:?}", kind),
+        },
+        kind => panic!("expected Function found {:?}", kind),
+    }
+}
+
+#[test]
+fn parser_leaves_nothing_over() {
+    let (rest, exprs) = parser("one = 1\n) 2\n").unwrap();
+    assert_eq!(rest, "");
+    assert!(matches!(exprs[1].kind, ExprKind::Error { .. }));
 }
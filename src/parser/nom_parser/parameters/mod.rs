@@ -4,13 +4,13 @@
  * Handles all Function Parameter in Grammer.
  */
 
-use super::common::trim;
+use super::common::{is_keyword, trim, Input};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{alpha1, alphanumeric1},
     combinator::map,
-    combinator::recognize,
+    combinator::{recognize, verify},
     error::{context, VerboseError},
     multi::many0,
     sequence::pair,
@@ -18,23 +18,26 @@ use nom::{
 };
 
 // ❌   paramenters  → IDENTIFIER ( IDENTIFIER )* ;
-pub fn parser(input: &str) -> IResult<&str, Vec<String>, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Vec<String>, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Parameter's");
     }
-    context("Declarations Parser", trim(many0(identifier)))(input)
+    context("Parameters Parser", trim(many0(identifier)))(input)
 }
 
 // TODO: FIXME: only use the IDENTIFIER parser from the primary module.
-pub fn identifier(input: &str) -> IResult<&str, String, VerboseError<&str>> {
+pub fn identifier(input: Input) -> IResult<Input, String, VerboseError<Input>> {
     context(
         "Not a paramenter IDENTIFIER",
         map(
-            trim(recognize(pair(
-                alt((alpha1, tag("_"))),
-                many0(alt((alphanumeric1, tag("_")))),
-            ))),
-            |keyword: &str| keyword.into(),
+            trim(verify(
+                recognize(pair(
+                    alt((alpha1, tag("_"))),
+                    many0(alt((alphanumeric1, tag("_")))),
+                )),
+                |word: &Input| !is_keyword(word.fragment()),
+            )),
+            |keyword: Input| keyword.to_string(),
         ),
     )(input)
 }
@@ -1,4 +1,4 @@
-use super::{trim, Constant};
+use super::{trim, Constant, Input};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -8,7 +8,7 @@ use nom::{
 };
 
 // BOOLEAN
-pub fn parser(input: &str) -> IResult<&str, Constant, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Constant, VerboseError<Input>> {
     context(
         "Not a Bool",
         alt((
@@ -20,6 +20,7 @@ pub fn parser(input: &str) -> IResult<&str, Constant, VerboseError<&str>> {
 
 #[test]
 fn boolean_parser() {
-    assert_eq!(parser(" true"), Ok(("", Constant::Boolean(true))));
-    assert_eq!(parser("false"), Ok(("", Constant::Boolean(false))));
+    use super::parse;
+    assert_eq!(parse(parser, " true"), Ok(("", Constant::Boolean(true))));
+    assert_eq!(parse(parser, "false"), Ok(("", Constant::Boolean(false))));
 }
@@ -1,3 +1,4 @@
+use super::Input;
 use nom::{
     character::complete::char,
     character::complete::one_of,
@@ -6,7 +7,7 @@ use nom::{
     sequence::terminated,
     IResult,
 };
-pub fn parser(input: &str) -> IResult<&str, String, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, String, VerboseError<Input>> {
     context(
         "Decimal",
         fold_many1(
@@ -19,6 +20,7 @@ pub fn parser(input: &str) -> IResult<&str, String, VerboseError<&str>> {
 
 #[test]
 fn decimal_parse() {
-    assert_eq!(parser("12_3_123"), Ok(("", "123123".into())));
-    assert_eq!(parser("123123"), Ok(("", "123123".into())));
+    use super::parse;
+    assert_eq!(parse(parser, "12_3_123"), Ok(("", "123123".into())));
+    assert_eq!(parse(parser, "123123"), Ok(("", "123123".into())));
 }
@@ -1,4 +1,4 @@
-use super::{decimal, Constant};
+use super::{decimal, Constant, Input};
 use nom::{
     branch::alt,
     character::complete::char,
@@ -11,16 +11,16 @@ use nom::{
     IResult,
 };
 // FLOAT
-pub fn parser(input: &str) -> IResult<&str, Constant, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Constant, VerboseError<Input>> {
     context(
         "Not a Float",
-        map(float, |float_str: &str| {
+        map(float, |float_str: Input| {
             Constant::Float(float_str.parse::<f64>().unwrap())
         }),
     )(input)
 }
 
-fn float(input: &str) -> IResult<&str, &str, VerboseError<&str>> {
+fn float(input: Input) -> IResult<Input, Input, VerboseError<Input>> {
     context(
         "Float",
         alt((
@@ -44,7 +44,8 @@ fn float(input: &str) -> IResult<&str, &str, VerboseError<&str>> {
 
 #[test]
 fn parser_float() {
-    assert_eq!(parser("1.23"), Ok(("", Constant::Float(1.23))));
-    assert_eq!(parser("1."), Ok(("", Constant::Float(1.))));
-    assert_eq!(parser(".23"), Ok(("", Constant::Float(0.23))));
+    use super::parse;
+    assert_eq!(parse(parser, "1.23"), Ok(("", Constant::Float(1.23))));
+    assert_eq!(parse(parser, "1."), Ok(("", Constant::Float(1.))));
+    assert_eq!(parse(parser, ".23"), Ok(("", Constant::Float(0.23))));
 }
@@ -1,10 +1,10 @@
-use super::{trim, Constant};
+use super::{is_keyword, trim, Constant, Input};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{alpha1, alphanumeric1},
     combinator::map,
-    combinator::recognize,
+    combinator::{recognize, verify},
     error::{context, VerboseError},
     multi::many0,
     sequence::pair,
@@ -12,27 +12,32 @@ use nom::{
 };
 
 // IDENTIFIER
-pub fn parser(input: &str) -> IResult<&str, Constant, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Constant, VerboseError<Input>> {
     context(
         "Not a IDENTIFIER",
         map(
-            trim(recognize(pair(
-                alt((alpha1, tag("_"))),
-                many0(alt((alphanumeric1, tag("_")))),
-            ))),
-            |keyword: &str| Constant::Keyword(keyword.into()),
+            trim(verify(
+                recognize(pair(
+                    alt((alpha1, tag("_"))),
+                    many0(alt((alphanumeric1, tag("_")))),
+                )),
+                |word: &Input| !is_keyword(word.fragment()),
+            )),
+            |keyword: Input| Constant::Keyword(keyword.to_string()),
         ),
     )(input)
 }
 
 #[test]
 fn identifier_parse() {
+    use super::parse;
     assert_eq!(
-        parser(" func_name "),
+        parse(parser, " func_name "),
         Ok(("", Constant::Keyword("func_name".into())))
     );
     assert_eq!(
-        parser(" func_name          = 1"),
+        parse(parser, " func_name          = 1"),
         Ok(("= 1", Constant::Keyword("func_name".into())))
     );
+    assert!(parse(parser, "then").is_err());
 }
@@ -1,11 +1,11 @@
-use super::{decimal, Constant};
+use super::{decimal, Constant, Input};
 use nom::{
     combinator::map,
     error::{context, VerboseError},
     IResult,
 };
 // INT
-pub fn parser(input: &str) -> IResult<&str, Constant, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Constant, VerboseError<Input>> {
     context(
         "Not a Int",
         map(decimal::parser, |digit_str: String| {
@@ -16,8 +16,9 @@ pub fn parser(input: &str) -> IResult<&str, Constant, VerboseError<&str>> {
 
 #[test]
 fn parse_int() {
+    use super::parse;
     let int = "123";
-    assert_eq!(parser(int), Ok(("", Constant::Int(123))));
+    assert_eq!(parse(parser, int), Ok(("", Constant::Int(123))));
     let not = "1.123";
-    assert_eq!(parser(not), Ok((".123", Constant::Int(1))));
+    assert_eq!(parse(parser, not), Ok((".123", Constant::Int(1))));
 }
@@ -1,9 +1,14 @@
-use super::{common::trim, statement, Constant, Expr};
+use super::{
+    common::{furthest, is_keyword, spanned, trim, Input},
+    list, statement, Constant, Expr, ExprKind,
+};
 use nom::{
-    branch::alt, bytes::complete::tag, combinator::map, error::VerboseError, sequence::delimited,
-    IResult,
+    character::complete::char, combinator::map, error::VerboseError, sequence::delimited, IResult,
 };
 
+#[cfg(test)]
+use super::common::parse;
+
 mod boolean;
 mod decimal;
 mod float;
@@ -11,17 +16,21 @@ mod identifier;
 mod int;
 mod string;
 
-// primary → FLOAT | INT | STRING | "true" | "false" | "(" expression ")" | IDENTIFIER | COMMENT;
-pub(crate) fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+// primary → FLOAT | INT | STRING | "true" | "false" | list | "(" expression ")" | IDENTIFIER | COMMENT;
+pub(crate) fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Primary's");
     }
-    alt((
-        map(trim(string::parser), Into::into),
-        map(trim(float::parser), Into::into),
-        map(trim(int::parser), Into::into),
-        map(trim(boolean::parser), Into::into),
-        map(trim(identifier::parser), Into::into),
-        trim(delimited(tag("("), statement::parser, tag(")"))),
-    ))(input)
+    furthest(
+        input,
+        &mut [
+            &mut trim(spanned(map(string::parser, ExprKind::Constant))),
+            &mut trim(spanned(map(float::parser, ExprKind::Constant))),
+            &mut trim(spanned(map(int::parser, ExprKind::Constant))),
+            &mut spanned(map(boolean::parser, ExprKind::Constant)),
+            &mut list::parser,
+            &mut spanned(map(identifier::parser, ExprKind::Constant)),
+            &mut trim(delimited(char('('), statement::parser, char(')'))),
+        ],
+    )
 }
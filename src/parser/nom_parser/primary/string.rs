@@ -1,16 +1,16 @@
-use super::Constant;
+use super::{Constant, Input};
 use nom::{
     branch::alt,
     bytes::streaming::{is_not, take_while_m_n},
     character::complete::{char, multispace1},
     combinator::{map, map_opt, map_res, value, verify},
-    error::{context, FromExternalError, ParseError, VerboseError},
+    error::{context, FromExternalError, ParseError, VerboseError, VerboseErrorKind},
     multi::fold_many0,
     sequence::{delimited, preceded},
     IResult,
 };
 // STRING
-pub fn parser(input: &str) -> IResult<&str, Constant, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Constant, VerboseError<Input>> {
     // From Examples in nom
     // fold_many0 is the equivalent of iterator::fold. It runs a parser in a loop,
     // and for each output value, calls a folding function on each output value.
@@ -35,18 +35,26 @@ pub fn parser(input: &str) -> IResult<&str, Constant, VerboseError<&str>> {
     // " character, the closing delimiter " would never match. When using
     // `delimited` with a looping parser (like fold_many0), be sure that the
     // loop won't accidentally match your closing delimiter!
-    map(
+    let result = map(
         context(
             "Not a String",
             delimited(char('"'), build_string, char('"')),
         ),
         Constant::String,
-    )(input)
+    )(input);
+    // The fragment parsers stream, so a string with no closing quote asks
+    // for more input.
+    match result {
+        Err(nom::Err::Incomplete(_)) => Err(nom::Err::Failure(VerboseError {
+            errors: vec![(input, VerboseErrorKind::Context("unterminated string"))],
+        })),
+        result => result,
+    }
 }
 
-fn parse_unicode<'a, E>(input: &'a str) -> IResult<&'a str, char, E>
+fn parse_unicode<'a, E>(input: Input<'a>) -> IResult<Input<'a>, char, E>
 where
-    E: ParseError<&'a str> + FromExternalError<&'a str, std::num::ParseIntError>,
+    E: ParseError<Input<'a>> + FromExternalError<Input<'a>, std::num::ParseIntError>,
 {
     // `take_while_m_n` parses between `m` and `n` bytes (inclusive) that match
     // a predicate. `parse_hex` here parses between 1 and 6 hexadecimal numerals.
@@ -65,7 +73,9 @@ where
     // `map_res` takes the result of a parser and applies a function that returns
     // a Result. In this case we take the hex bytes from parse_hex and attempt to
     // convert them to a u32.
-    let parse_u32 = map_res(parse_delimited_hex, move |hex| u32::from_str_radix(hex, 16));
+    let parse_u32 = map_res(parse_delimited_hex, move |hex: Input| {
+        u32::from_str_radix(hex.fragment(), 16)
+    });
 
     // map_opt is like map_res, but it takes an Option instead of a Result. If
     // the function returns None, map_opt returns an error. In this case, because
@@ -75,9 +85,9 @@ where
 }
 
 /// Parse an escaped character: \n, \t, \r, \u{00AC}, etc.
-fn parse_escaped_char<'a, E>(input: &'a str) -> IResult<&'a str, char, E>
+fn parse_escaped_char<'a, E>(input: Input<'a>) -> IResult<Input<'a>, char, E>
 where
-    E: ParseError<&'a str> + FromExternalError<&'a str, std::num::ParseIntError>,
+    E: ParseError<Input<'a>> + FromExternalError<Input<'a>, std::num::ParseIntError>,
 {
     preceded(
         char('\\'),
@@ -103,14 +113,16 @@ where
 
 /// Parse a backslash, followed by any amount of whitespace. This is used later
 /// to discard any escaped whitespace.
-fn parse_escaped_whitespace<'a, E: ParseError<&'a str>>(
-    input: &'a str,
-) -> IResult<&'a str, &'a str, E> {
+fn parse_escaped_whitespace<'a, E: ParseError<Input<'a>>>(
+    input: Input<'a>,
+) -> IResult<Input<'a>, Input<'a>, E> {
     preceded(char('\\'), multispace1)(input)
 }
 
 /// Parse a non-empty block of text that doesn't include \ or "
-fn parse_literal<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+fn parse_literal<'a, E: ParseError<Input<'a>>>(
+    input: Input<'a>,
+) -> IResult<Input<'a>, Input<'a>, E> {
     // `is_not` parses a string of 0 or more characters that aren't one of the
     // given characters.
     let not_quote_slash = is_not("\"\\");
@@ -119,7 +131,7 @@ fn parse_literal<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str,
     // the parser. The verification function accepts out output only if it
     // returns true. In this case, we want to ensure that the output of is_not
     // is non-empty.
-    verify(not_quote_slash, |s: &str| !s.is_empty())(input)
+    verify(not_quote_slash, |s: &Input| !s.is_empty())(input)
 }
 
 /// A string fragment contains a fragment of a string being parsed: either
@@ -134,14 +146,16 @@ enum StringFragment<'a> {
 
 /// Combine parse_literal, parse_escaped_whitespace, and parse_escaped_char
 /// into a StringFragment.
-fn parse_fragment<'a, E>(input: &'a str) -> IResult<&'a str, StringFragment<'a>, E>
+fn parse_fragment<'a, E>(input: Input<'a>) -> IResult<Input<'a>, StringFragment<'a>, E>
 where
-    E: ParseError<&'a str> + FromExternalError<&'a str, std::num::ParseIntError>,
+    E: ParseError<Input<'a>> + FromExternalError<Input<'a>, std::num::ParseIntError>,
 {
     alt((
         // The `map` combinator runs a parser, then applies a function to the output
         // of that parser.
-        map(parse_literal, StringFragment::Literal),
+        map(parse_literal, |s: Input<'a>| {
+            StringFragment::Literal(s.fragment())
+        }),
         map(parse_escaped_char, StringFragment::EscapedChar),
         value(StringFragment::EscapedWS, parse_escaped_whitespace),
     ))(input)
@@ -149,17 +163,19 @@ where
 
 #[test]
 fn string_parse() {
+    use super::parse;
     let data = "\"This is a long string.\"";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok(("", Constant::String("This is a long string.".into())))
     );
 }
 #[test]
 fn string_emojis_parse() {
+    use super::parse;
     let data = "\"\\\" <- or even 😏 ✅ ❌ 🌎 🦀\"";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok(("", Constant::String("\" <- or even 😏 ✅ ❌ 🌎 🦀".into())))
     );
 }
@@ -1,14 +1,175 @@
 /*
  * program/mod.rs
  */
-use super::{declaration, Expr};
+use super::common::{block_comment, doc_comments, position, Input};
+use super::{declaration, Expr, ExprKind, Span};
+use crate::error::{label, notes};
 
-use nom::{error::VerboseError, multi::many0, IResult};
+use nom::{
+    error::{ErrorKind, ParseError, VerboseError},
+    IResult, InputTake,
+};
+
+#[cfg(test)]
+use super::common::parse;
 
 // program      → declaration* EOF ;
-pub fn parser(input: &str) -> IResult<&str, Vec<Expr>, VerboseError<&str>> {
+//
+// A declaration that does not parse becomes an ExprKind::Error and parsing
+// picks up again at the next line starting in column 0 or after the next "}".
+pub fn parser(input: Input) -> IResult<Input, Vec<Expr>, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Starting Parse");
     }
-    many0(declaration::parser)(input)
+    let mut exprs = Vec::new();
+    let (mut input, mut doc) = doc_comments(input)?;
+    while !input.fragment().is_empty() {
+        let rest = match declaration::parser(input) {
+            Ok((rest, _)) if rest.location_offset() == input.location_offset() => {
+                let error = VerboseError::from_error_kind(input, ErrorKind::Many0);
+                return Err(nom::Err::Error(error));
+            }
+            Ok((rest, expr)) => {
+                exprs.push(documented(expr, doc.take()));
+                rest
+            }
+            Err(nom::Err::Error(error)) | Err(nom::Err::Failure(error)) => {
+                let (expr, stopped) = error_node(input, &error);
+                exprs.push(expr);
+                synchronise(stopped)
+            }
+            Err(e) => return Err(e),
+        };
+        (input, doc) = doc_comments(rest)?;
+    }
+    Ok((input, exprs))
+}
+
+/// Gives a Function the doc comments written above it.
+fn documented(mut expr: Expr, doc: Option<String>) -> Expr {
+    if let ExprKind::Function(_, _, _, function_doc) = &mut expr.kind {
+        *function_doc = doc;
+    }
+    expr
+}
+
+/// An ExprKind::Error for `error` and the input where the parser stopped.
+fn error_node<'a>(input: Input<'a>, error: &VerboseError<Input<'a>>) -> (Expr, Input<'a>) {
+    // `at` can be a slice of a function body, so take the rest from `input`.
+    // When the body ran out what follows it belongs to the next declaration,
+    // so the Error goes right after the body's last code instead.
+    let (stopped, ran_out) = match error.errors.first() {
+        Some((at, _)) if at.location_offset() > input.location_offset() => {
+            let offset = at.location_offset() - input.location_offset();
+            match at.fragment().trim().is_empty() {
+                true => (input.fragment()[..offset].trim_end().len(), true),
+                false => (offset, false),
+            }
+        }
+        _ => (0, false),
+    };
+    let stopped = input.take_split(stopped).0;
+    let next = match ran_out {
+        true => String::new(),
+        false => stopped
+            .fragment()
+            .chars()
+            .next()
+            .map(String::from)
+            .unwrap_or_default(),
+    };
+    let label = match error.errors.first() {
+        Some((_, kind)) => label(kind, &next),
+        None => format!("unexpected `{}`", next),
+    };
+    let kind = ExprKind::Error {
+        label,
+        notes: notes(error.errors.iter().skip(1).map(|(_, kind)| kind)),
+    };
+    let start = position(stopped);
+    (
+        Expr::new(kind, Span::new(start, start.advance(&next))),
+        stopped,
+    )
+}
+
+/// Skips past the next "}" or to the next line that starts in column 0,
+/// passing over block comments whole.
+fn synchronise(input: Input) -> Input {
+    let text = input.fragment();
+    let mut index = 0;
+    while let Some(c) = text[index..].chars().next() {
+        if text[index..].starts_with("/*") {
+            index = match block_comment(input.take_split(index).0) {
+                Ok((after, _)) => after.location_offset() - input.location_offset(),
+                Err(_) => text.len(),
+            };
+            continue;
+        }
+        index += c.len_utf8();
+        match c {
+            '}' => break,
+            '\n' if text[index..].starts_with(|c: char| !c.is_whitespace()) => break,
+            _ => {}
+        }
+    }
+    input.take_split(index).0
+}
+
+#[test]
+fn recovers_at_declarations() {
+    let (rest, exprs) = parse(parser, "one = 1\ntwo = * 2\nthree = 3\n) 4\nfive = 5\n").unwrap();
+    assert_eq!(rest, "");
+    let errors: Vec<_> = exprs
+        .iter()
+        .filter(|expr| matches!(expr.kind, ExprKind::Error { .. }))
+        .map(|expr| (expr.span.start.line, expr.span.start.column))
+        .collect();
+    assert_eq!(errors, vec![(2, 7), (4, 1)]);
+    assert_eq!(exprs.len(), 5);
+    let (_, exprs) = parse(parser, "one = ) /* a\n} b\n*/\ntwo = 2\n").unwrap();
+    let kinds: Vec<_> = exprs
+        .iter()
+        .map(|expr| matches!(expr.kind, ExprKind::Error { .. }))
+        .collect();
+    assert_eq!(kinds, vec![true, false]);
+}
+
+#[test]
+fn errors_are_where_parsing_stopped() {
+    let error = |source| match parse(parser, source).unwrap().1.remove(0) {
+        Expr {
+            kind: ExprKind::Error { label, .. },
+            span,
+        } => (span.start.line, span.start.column, label),
+        expr => panic!("expected Error found {:?}", expr),
+    };
+    assert_eq!(error("x = )\n"), (1, 5, "unexpected `)`".into()));
+    assert_eq!(error("b = (2\nc = 3\n"), (1, 7, "expected `)`".into()));
+    assert_eq!(error("x = \"abc\n"), (1, 5, "unterminated string".into()));
+    // Parsing carries on after both.
+    let (_, exprs) = parse(parser, "b = (2\nx = \"abc\ny = )\n").unwrap();
+    assert_eq!(exprs.len(), 3);
+    assert_eq!(
+        error("f x =\n    /* a\n    x + 1\n"),
+        (2, 5, "unterminated block comment".into())
+    );
+}
+
+#[test]
+fn doc_comments_go_on_functions() {
+    let source = "/// One.\none = 1\n/// Not a function.\nprint one\n// two\ntwo = 2 /* done */";
+    let (rest, exprs) = parse(parser, source).unwrap();
+    assert_eq!(rest, "");
+    let docs: Vec<_> = exprs
+        .iter()
+        .map(|expr| match &expr.kind {
+            ExprKind::Function(name, _, _, doc) => Some((name.as_str(), doc.as_deref())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        docs,
+        vec![Some(("one", Some("One."))), None, Some(("two", None))]
+    );
 }
@@ -1,19 +1,93 @@
 /*
  * statement/mod.rs
  */
-use super::{block, conditional, expression, print, Expr};
+use super::common::{furthest, trim, Input};
+use super::{block, conditional, expression, Expr, ExprKind};
 
-use nom::{branch::alt, error::VerboseError, IResult};
+use nom::{
+    bytes::complete::tag,
+    error::{VerboseError, VerboseErrorKind},
+    multi::separated_list1,
+    IResult,
+};
 
-// statement    → printStmt | expression | ifStmt | returnStmt | block ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+#[cfg(test)]
+use super::common::parse;
+
+// statement    → single ( ";" single )* ;
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for a Statement");
     }
-    alt((
-        print::parser,
-        conditional::parser,
-        expression::parser,
-        block::parser,
-    ))(input)
+    let (i, mut singles) = separated_list1(trim(tag(";")), single)(input)?;
+    if let Ok((_, semicolon)) = trim(tag(";"))(i) {
+        let context = VerboseErrorKind::Context("expected a statement after `;`");
+        return Err(nom::Err::Failure(VerboseError {
+            errors: vec![(semicolon, context)],
+        }));
+    }
+    if singles.len() == 1 {
+        return Ok((i, singles.remove(0)));
+    }
+    let span = singles[0].span.to(singles[singles.len() - 1].span);
+    Ok((i, Expr::new(ExprKind::Sequence(singles), span)))
+}
+
+// single       → expression | ifStmt | returnStmt | block ;
+pub fn single(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
+    furthest(
+        input,
+        &mut [
+            &mut conditional::parser,
+            &mut expression::parser,
+            &mut block::parser,
+        ],
+    )
+}
+
+#[test]
+fn statement_sequence() {
+    use super::Constant;
+    let (rest, expr) = parse(parser, "print \"hi\"; 1 ;2\n").unwrap();
+    assert_eq!(rest, "");
+    match expr.kind {
+        ExprKind::Sequence(singles) => {
+            assert_eq!(singles.len(), 3);
+            assert!(matches!(singles[0].kind, ExprKind::Call(..)));
+            assert_eq!(singles[2], Constant::Int(2).into());
+        }
+        kind => panic!("expected Sequence found {:?}", kind),
+    }
+    assert_eq!(expr.span.start.column, 1);
+    assert_eq!(expr.span.end.column, 17);
+}
+
+#[test]
+fn semicolon_errors() {
+    let failure = |source| match parse(parser, source) {
+        Err(nom::Err::Failure(error)) => {
+            let (at, kind) = &error.errors[0];
+            (at.get_utf8_column(), kind.clone())
+        }
+        result => panic!("expected Failure found {:?}", result),
+    };
+    assert_eq!(
+        failure("if x then println \"a\"; 1 else 2"),
+        (
+            22,
+            VerboseErrorKind::Context(
+                "a `;` sequence needs parentheses inside `then`, `then (a; b) else c`"
+            )
+        )
+    );
+    assert_eq!(
+        failure("1 ;"),
+        (
+            3,
+            VerboseErrorKind::Context("expected a statement after `;`")
+        )
+    );
+    let (rest, expr) = parse(parser, "if x then (println \"a\"; 1) else 2").unwrap();
+    assert_eq!(rest, "");
+    assert!(matches!(expr.kind, ExprKind::IfElse(..)));
 }
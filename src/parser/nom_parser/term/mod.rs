@@ -4,7 +4,11 @@
  * This file Handles the Unary Grammer
  */
 
-use super::{common, factor, Expr, Operator};
+use super::{
+    common,
+    common::{binary, Input},
+    factor, Expr, Operator,
+};
 
 use nom::{
     branch::alt,
@@ -17,7 +21,7 @@ use nom::{
 mod term_op;
 
 // term           → factor ( ( "-" | "+" ) factor )* ;
-pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Term.");
     }
@@ -26,73 +30,70 @@ pub fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
 
 #[test]
 fn term_parse() {
-    use super::Constant;
+    use super::common::parse;
+    use super::{Constant, ExprKind};
     let data = " 1 + 2 ";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Binary {
+            Expr::from(ExprKind::Binary {
                 op: Operator::Plus,
                 lhs: Box::new(Constant::Int(1).into()),
                 rhs: Box::new(Constant::Int(2).into()),
-            }
+            })
         ))
     );
 
     let data = "// lkjdsfaklsfjkjasdflkjdafs \n!true";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Unary {
+            Expr::from(ExprKind::Unary {
                 op: Operator::Bang,
                 child: Box::new(Constant::Boolean(true).into())
-            }
+            })
         ))
     );
 }
 
 #[test]
 fn term_parser_mult_depth() {
-    use super::Constant;
+    use super::common::parse;
+    use super::{Constant, ExprKind};
     let data = "1 + 1 + 1 + 1";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Binary {
+            Expr::from(ExprKind::Binary {
                 op: Operator::Plus,
-                lhs: Box::new(Expr::Binary {
+                lhs: Box::new(Expr::from(ExprKind::Binary {
                     op: Operator::Plus,
-                    lhs: Box::new(Expr::Binary {
+                    lhs: Box::new(Expr::from(ExprKind::Binary {
                         op: Operator::Plus,
-                        lhs: Box::new(Expr::Constant(Constant::Int(1))),
-                        rhs: Box::new(Expr::Constant(Constant::Int(1)))
-                    }),
-                    rhs: Box::new(Expr::Constant(Constant::Int(1)))
-                }),
-                rhs: Box::new(Expr::Constant(Constant::Int(1)))
-            }
+                        lhs: Box::new(Expr::from(Constant::Int(1))),
+                        rhs: Box::new(Expr::from(Constant::Int(1)))
+                    })),
+                    rhs: Box::new(Expr::from(Constant::Int(1)))
+                })),
+                rhs: Box::new(Expr::from(Constant::Int(1)))
+            })
         ))
     );
 }
 
-pub fn term_parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub fn term_parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     let (i, lhs) = factor::parser(input)?;
     let (i, op) = term_op::parser(i)?;
     let (i, rhs) = factor::parser(i)?;
-    let lhs = Box::new(lhs);
-    let rhs = Box::new(rhs);
-    let expr = Expr::Binary { op, lhs, rhs };
+    let expr = binary(op, lhs, rhs);
     match many1(pair(term_op::parser, factor::parser))(i) {
         Ok((i, vec)) => Ok((
             i,
-            vec.iter().fold(expr, |lhs, (op, rhs)| Expr::Binary {
-                op: *op,
-                lhs: Box::new(lhs),
-                rhs: Box::new(rhs.clone()),
-            }),
+            vec.into_iter()
+                .fold(expr, |lhs, (op, rhs)| binary(op, lhs, rhs)),
         )),
         Err(_) => Ok((i, expr)),
     }
@@ -3,7 +3,10 @@
  *
  * This parser Handles turning &str into Operator Type
  */
-use super::{common::trim, Operator};
+use super::{
+    common::{trim, Input},
+    Operator,
+};
 use nom::{
     character::complete::one_of,
     combinator::map,
@@ -12,7 +15,7 @@ use nom::{
 };
 
 // Parser looks for a + or -.
-pub(crate) fn parser(input: &str) -> IResult<&str, Operator, VerboseError<&str>> {
+pub(crate) fn parser(input: Input) -> IResult<Input, Operator, VerboseError<Input>> {
     context(
         "Not a + or - Operator",
         map(trim(one_of("+-")), |c: char| match c {
@@ -25,6 +28,7 @@ pub(crate) fn parser(input: &str) -> IResult<&str, Operator, VerboseError<&str>>
 
 #[test]
 fn unary_minus_parser() {
-    assert_eq!(parser(" + "), Ok(("", Operator::Plus,)));
-    assert_eq!(parser(" - "), Ok(("", Operator::Minus,)));
+    use super::common::parse;
+    assert_eq!(parse(parser, " + "), Ok(("", Operator::Plus,)));
+    assert_eq!(parse(parser, " - "), Ok(("", Operator::Minus,)));
 }
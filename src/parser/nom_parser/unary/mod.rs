@@ -3,7 +3,11 @@
  *
  * This parser Handles turning &str into Operator Type
  */
-use super::{call, common, primary, Expr, Operator};
+use super::{
+    call, common,
+    common::{start, Input},
+    primary, Expr, ExprKind, Operator, Span,
+};
 use nom::{
     branch::alt,
     combinator::map,
@@ -15,7 +19,7 @@ use nom::{
 mod unary_op;
 
 // unary          → ( "!" | "-" ) unary | call ;
-pub(crate) fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+pub(crate) fn parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Term.");
     }
@@ -27,33 +31,34 @@ pub(crate) fn parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
 
 #[test]
 fn unary_parser() {
-    use super::{Constant, Operator};
+    use super::common::parse;
+    use super::Constant;
     let data = "-123432";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Unary {
+            Expr::from(ExprKind::Unary {
                 op: Operator::Minus,
                 child: Box::new(Constant::Int(123432).into())
-            }
+            })
         ))
     );
 
     let data = "!true";
     assert_eq!(
-        parser(data),
+        parse(parser, data),
         Ok((
             "",
-            Expr::Unary {
+            Expr::from(ExprKind::Unary {
                 op: Operator::Bang,
                 child: Box::new(Constant::Boolean(true).into())
-            }
+            })
         ))
     );
 }
 
-fn many1_unary_op_parser(input: &str) -> IResult<&str, Option<Operator>, VerboseError<&str>> {
+fn many1_unary_op_parser(input: Input) -> IResult<Input, Option<Operator>, VerboseError<Input>> {
     map(many1(unary_op::parser), |vec_op| {
         if vec_op.len() % 2 == 0 {
             None
@@ -63,14 +68,14 @@ fn many1_unary_op_parser(input: &str) -> IResult<&str, Option<Operator>, Verbose
     })(input)
 }
 
-fn unary_const_or_unary_parser(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
-    let (i, some_op) = many1_unary_op_parser(input)?;
+fn unary_const_or_unary_parser(input: Input) -> IResult<Input, Expr, VerboseError<Input>> {
+    let (i, start) = start(input)?;
+    let (i, some_op) = many1_unary_op_parser(i)?;
     let (i, child) = primary::parser(i)?;
     let expr = if let Some(op) = some_op {
-        Expr::Unary {
-            op,
-            child: Box::new(child),
-        }
+        let span = Span::new(start, child.span.end);
+        let child = Box::new(child);
+        Expr::new(ExprKind::Unary { op, child }, span)
     } else {
         child
     };
@@ -79,20 +84,21 @@ fn unary_const_or_unary_parser(input: &str) -> IResult<&str, Expr, VerboseError<
 
 #[test]
 fn fail_unary_test() {
+    use super::common::parse;
     use super::Constant;
-    assert!(unary_const_or_unary_parser("1").ok().is_none());
+    assert!(parse(unary_const_or_unary_parser, "1").ok().is_none());
     assert_eq!(
-        unary_const_or_unary_parser("-1"),
+        parse(unary_const_or_unary_parser, "-1"),
         Ok((
             "",
-            Expr::Unary {
+            Expr::from(ExprKind::Unary {
                 op: Operator::Minus,
-                child: Box::new(Expr::Constant(Constant::Int(1)))
-            }
+                child: Box::new(Expr::from(Constant::Int(1)))
+            })
         ))
     );
     assert_eq!(
-        alt((unary_const_or_unary_parser, primary::parser))("1"),
-        Ok(("", Expr::Constant(Constant::Int(1))))
+        parse(alt((unary_const_or_unary_parser, primary::parser)), "1"),
+        Ok(("", Expr::from(Constant::Int(1))))
     );
 }
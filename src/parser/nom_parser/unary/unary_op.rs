@@ -1,4 +1,4 @@
-use super::common::trim;
+use super::common::{trim, Input};
 use super::Operator;
 use nom::{
     character::complete::one_of,
@@ -8,7 +8,7 @@ use nom::{
 };
 
 // Parser looks for a - or ! and returns a Operator enum.
-pub(crate) fn parser(input: &str) -> IResult<&str, Operator, VerboseError<&str>> {
+pub(crate) fn parser(input: Input) -> IResult<Input, Operator, VerboseError<Input>> {
     if cfg!(feature = "announce") {
         println!("Checking for Unary Op");
     }
@@ -24,6 +24,7 @@ pub(crate) fn parser(input: &str) -> IResult<&str, Operator, VerboseError<&str>>
 
 #[test]
 fn unary_op_parser() {
-    assert_eq!(parser(" ! "), Ok(("", Operator::Bang,)));
-    assert_eq!(parser(" - "), Ok(("", Operator::Minus,)));
+    use super::common::parse;
+    assert_eq!(parse(parser, " ! "), Ok(("", Operator::Bang,)));
+    assert_eq!(parse(parser, " - "), Ok(("", Operator::Minus,)));
 }
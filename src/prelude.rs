@@ -0,0 +1,29 @@
+use crate::error;
+use crate::interpreter::{interpreter, DataStruct, Environment};
+use crate::parser::{self, Expr};
+
+const PRELUDE: &str = include_str!("prelude.uln");
+
+/// Evaluates `prelude.uln` into a new Environment.
+pub fn load() -> Environment {
+    let (_, exprs) = parser::parser(PRELUDE).expect("prelude.uln failed to parse");
+    let mut exprs = error::parsed(exprs).expect("prelude.uln failed to parse");
+    // Errors in the prelude point at the code that called it instead.
+    exprs.iter_mut().for_each(Expr::clear_spans);
+    let (_, env) = interpreter(exprs, Environment::new()).expect("prelude.uln failed to run");
+    env.into_iter()
+        .map(|(name, data_struct)| match data_struct {
+            DataStruct::Function(n, param, stmt) => (name, DataStruct::Prelude(n, param, stmt)),
+            DataStruct::Value(n, stmt, _) => (name, DataStruct::Prelude(n, Vec::new(), stmt)),
+            data_struct => (name, data_struct),
+        })
+        .collect()
+}
+
+#[test]
+fn prelude_parses_and_loads() {
+    let (leftover, _) = parser::parser(PRELUDE).unwrap();
+    assert_eq!(leftover, "");
+    let env = load();
+    assert!(matches!(env.get("compose"), Some(DataStruct::Prelude(..))));
+}
@@ -9,26 +9,30 @@ use rustyline::validate::{self, MatchingBracketValidator, Validator};
 use rustyline::{Cmd, CompletionType, Config, Context, EditMode, Editor, KeyEvent};
 use rustyline_derive::Helper;
 
-use crate::interpreter::{interpreter_expr, Environment};
+use crate::error::{self, Error};
+use crate::files::{print_value, report};
+use crate::interpreter::{interpreter, Environment};
 use crate::parser::parser;
 fn run_block(block: &str, mut env: Environment) -> Environment {
     if block.is_empty() {
         return env;
     }
-    match parser(block) {
-        Ok((input, expr)) => {
-            for ex in expr {
-                let (cons_vec, e) = interpreter_expr(ex, env);
+    let parsed = parser(block).map_err(|e| Error::syntax(block, &e));
+    let exprs = match parsed.and_then(|(_, exprs)| error::parsed(exprs)) {
+        Ok(exprs) => exprs,
+        Err(e) => {
+            report(&e, "<repl>", block);
+            return env;
+        }
+    };
+    for ex in exprs {
+        match interpreter(vec![ex], env.clone()) {
+            Ok((cons_vec, e)) => {
                 env = e;
-                for cons in cons_vec {
-                    println!("[OUT]: {:?}", cons);
-                    if !input.is_empty() {
-                        println!("[LEFTOVER]: {:?}", input);
-                    }
-                }
+                cons_vec.iter().for_each(print_value);
             }
+            Err(e) => report(&e, "<repl>", block),
         }
-        Err(e) => println!("[ERROR]: {:#?}", e),
     }
     env
 }
@@ -47,7 +51,7 @@ fn shell_help() {
         hello = {green}\"Hello\"{reset}
         space = {green}\" \"{reset}
         world = {green}\"World\"{reset}
-        {cyan}print{reset} hello + space + world {reset}{reset_font}
+        {cyan}print{reset} (hello + space + world) {reset}{reset_font}
                          ",
             green = "\x1b[32m",
             reset = "\x1b[37m",
@@ -127,7 +131,7 @@ impl Validator for MyHelper {
     }
 }
 
-pub fn run() -> rustyline::Result<()> {
+pub fn run(mut env: Environment) -> rustyline::Result<()> {
     env_logger::init();
     let config = Config::builder()
         .history_ignore_space(true)
@@ -149,7 +153,6 @@ pub fn run() -> rustyline::Result<()> {
     if rl.load_history("history.txt").is_err() {
         println!("No previous history.");
     }
-    let mut env = Environment::new();
     let mut count = 1;
     loop {
         let p = format!("IN [{}]: ", count);